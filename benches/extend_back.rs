@@ -0,0 +1,43 @@
+//! Compare pushing nodes one by one via `push_back` with `extend_back`.
+//!
+//! Run with `cargo bench --bench extend_back`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use concurrent_read_push_list::intrusive_list::*;
+
+type Node = IntrusiveListNodeImpl<usize>;
+
+const NODES: usize = 10_000;
+const ROUNDS: u32 = 100;
+
+fn bench(name: &str, f: impl Fn(&IntrusiveList<'_, Node>, &[Node])) {
+    let mut total = Duration::ZERO;
+    for _ in 0..ROUNDS {
+        // Nodes cannot be pushed again while still linked, so create new
+        // ones for each round.
+        let nodes: Vec<Node> = (0..NODES).map(Node::new).collect();
+        let list = IntrusiveList::new();
+
+        let start = Instant::now();
+        f(&list, &nodes);
+        total += start.elapsed();
+
+        black_box(&list);
+    }
+
+    println!("{:12} {:?} per {} nodes", name, total / ROUNDS, NODES);
+}
+
+fn main() {
+    bench("push_back", |list, nodes| {
+        for node in nodes {
+            unsafe { list.push_back(node) };
+        }
+    });
+
+    bench("extend_back", |list, nodes| {
+        unsafe { list.extend_back(nodes) };
+    });
+}
@@ -0,0 +1,57 @@
+//! Measure throughput of concurrent `push_front` and `push_back`, which
+//! is affected by false sharing between `first_ptr` and `last_ptr`.
+//!
+//! Compare `cargo bench --bench push_front_back` with
+//! `cargo bench --bench push_front_back --features cache_padded`.
+
+use std::thread;
+use std::time::{Duration, Instant};
+
+use concurrent_read_push_list::intrusive_list::*;
+
+type Node = IntrusiveListNodeImpl<usize>;
+
+/// # num of threads pushing to each end
+const THREADS: usize = 2;
+/// # num of nodes pushed by each thread
+const NODES: usize = 10_000;
+const ROUNDS: u32 = 20;
+
+fn main() {
+    let mut total = Duration::ZERO;
+    for _ in 0..ROUNDS {
+        // Nodes cannot be pushed again while still linked, so create new
+        // ones for each round.
+        let nodes: Vec<Vec<Node>> = (0..2 * THREADS)
+            .map(|_| (0..NODES).map(Node::new).collect())
+            .collect();
+        let list = IntrusiveList::new();
+
+        let start = Instant::now();
+        thread::scope(|s| {
+            let list = &list;
+            for (index, nodes) in nodes.iter().enumerate() {
+                s.spawn(move || {
+                    for node in nodes {
+                        if index % 2 == 0 {
+                            unsafe { list.push_front(node) };
+                        } else {
+                            unsafe { list.push_back(node) };
+                        }
+                    }
+                });
+            }
+        });
+        total += start.elapsed();
+
+        assert_eq!(2 * THREADS * NODES, list.iter().count());
+    }
+
+    let pushes = (2 * THREADS * NODES) as f64;
+    println!(
+        "cache_padded = {}: {:?} per round, {:.1} Mpush/s",
+        cfg!(feature = "cache_padded"),
+        total / ROUNDS,
+        pushes * f64::from(ROUNDS) / total.as_secs_f64() / 1e6
+    );
+}
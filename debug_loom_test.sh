@@ -6,6 +6,9 @@ export LOOM_LOG=1
 export LOOM_LOCATION=1
 
 export LOOM_MAX_BRANCHES=100000
+# Bound the preemptions so that tests with spinning threads, e.g.
+# `test_list_push_back_stress`, can finish
+export LOOM_MAX_PREEMPTIONS=2
 
 feature="permutation_testing"
 
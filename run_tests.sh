@@ -6,6 +6,11 @@ run_test() {
     cargo test $args --target-dir target-"$1" --features "$1" ${@:2}
 }
 
+# Verify that the crate builds on a target without `std`
+check_no_std() {
+    cargo build $args --target-dir target-no_std --target thumbv7m-none-eabi --features "$1"
+}
+
 run_miri() {
     cargo +nightly miri test $args --target-dir miri-target-"$1" --features "$1" ${@:2}
 }
@@ -13,19 +18,32 @@ run_miri() {
 main() {
     export RUST_BACKTRACE=1
     # Allow environment variables to pass through
-    export MIRIFLAGS="-Zmiri-disable-isolation"
+    export MIRIFLAGS="-Zmiri-disable-isolation -Zmiri-strict-provenance"
     export LANG=C.UTF8
 
     export LOOM_MAX_BRANCHES=100000
+    # Bound the preemptions so that tests with spinning threads, e.g.
+    # `test_list_push_back_stress`, can finish
+    export LOOM_MAX_PREEMPTIONS=2
 
-    run_test default $@
-    run_test default --release $@
+    run_test default --features serde $@
+    run_test default --features serde --release $@
 
     run_test permutation_testing --release $@
 
-    # Temporarily disable miri due to "unimplemented sysconf name" on the workflow
-    #run_miri default $@
-    #run_miri default ---release $@
+    check_no_std ""
+    check_no_std alloc
+    check_no_std alloc,serde
+
+    # Check the pointer casts and the nodes freed by the owning lists under
+    # both borrow models.
+    #
+    # The whole test suite is not run due to "unimplemented sysconf name"
+    # on the workflow.
+    for filter in test_list_provenance owned_intrusive_list owning_forward_list; do
+        run_miri default $filter $@
+        MIRIFLAGS="$MIRIFLAGS -Zmiri-tree-borrows" run_miri default $filter $@
+    done
 }
 
 if [ -z ${CLEARED+x} ]; then
@@ -1,4 +1,11 @@
-use concurrency_toolkit::atomic::AtomicPtr;
+use core::marker::PhantomData;
+use core::ptr;
+use core::iter::{Iterator, IntoIterator};
+use core::fmt::{self, Debug, Formatter};
+
+use crate::utility::atomic::{AtomicPtr, Ordering};
+
+use crate::utility::*;
 
 /// Singly linked intrusive list node.
 ///
@@ -16,3 +23,226 @@ pub unsafe trait IntrusiveForwardListNode<'a> {
 
     fn get_elem(&'a self) -> Self::Target;
 }
+
+/// Sample implementation of IntrusiveForwardListNode
+#[derive(Debug)]
+pub struct IntrusiveForwardListNodeImpl<T> {
+    next_ptr: AtomicPtr<()>,
+    elem: T,
+}
+impl<T> IntrusiveForwardListNodeImpl<T> {
+    pub fn new(elem: T) -> Self {
+        Self {
+            next_ptr: AtomicPtr::new(ptr::null_mut()),
+            elem,
+        }
+    }
+}
+impl<T: Default> Default for IntrusiveForwardListNodeImpl<T> {
+    fn default() -> Self {
+        Self::new(Default::default())
+    }
+}
+unsafe impl<'a, T: 'a> IntrusiveForwardListNode<'a> for IntrusiveForwardListNodeImpl<T> {
+    type Target = &'a T;
+
+    fn get_next_ptr(&self) -> &AtomicPtr<()> {
+        &self.next_ptr
+    }
+    fn get_elem(&'a self) -> Self::Target {
+        &self.elem
+    }
+}
+
+/// IntrusiveForwardList guarantees that
+///  - push_front and read can be done concurrently while allowing stale read;
+///  - deletion can only be done sequentially when there is no
+///    writer (excluding the thread doing deletion) or reader.
+///
+/// It is suggested to use this with `RwLock`
+pub struct IntrusiveForwardList<'a, Node: IntrusiveForwardListNode<'a>> {
+    first_ptr: AtomicPtr<()>,
+    phantom: PhantomData<&'a Node>,
+}
+impl<'a, Node: IntrusiveForwardListNode<'a> + Debug> Debug for IntrusiveForwardList<'a, Node> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_list().entries(self).finish()
+    }
+}
+impl<'a, Node: IntrusiveForwardListNode<'a>> Default for IntrusiveForwardList<'a, Node> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<'a, Node: IntrusiveForwardListNode<'a>> IntrusiveForwardList<'a, Node> {
+    pub fn new() -> Self {
+        Self {
+            first_ptr: AtomicPtr::new(ptr::null_mut()),
+            phantom: PhantomData,
+        }
+    }
+
+    /// # Safety
+    ///
+    ///  * `node` -  __**YOU MUST NOT USE IT IN OTHER LISTS SIMULTANEOUSLY OR
+    ///    ADD IT TO THE SAME LIST SIMULTANEOUSLY**__.
+    pub unsafe fn push_front(&self, node: &'a Node) {
+        let node_ptr = node as *const _ as *mut ();
+
+        loop {
+            let first = self.first_ptr.load(R_ORD);
+
+            node.get_next_ptr().store(first, W_ORD);
+
+            match self.first_ptr
+                .compare_exchange_weak(first, node_ptr, RW_ORD, R_ORD)
+            {
+                Ok(_) => break,
+                Err(_) => {
+                    spin_wait();
+                    continue
+                },
+            }
+        }
+    }
+
+    // All methods read the list:
+
+    pub fn iter(&self) -> IntrusiveForwardListIterator<'a, '_, Node> {
+        IntrusiveForwardListIterator {
+            curr_ptr: self.first_ptr.load(R_ORD),
+            phantom0: PhantomData,
+            phantom1: PhantomData,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.first_ptr.load(R_ORD).is_null()
+    }
+
+    // All methods below are removal methods, which takes the write lock:
+
+    ///  * `f` - return true to remove the node or false to keep it
+    ///
+    /// Return (# num of elements left, # num of elements removed)
+    pub fn remove_if(&mut self, mut f: impl FnMut(&'a Node) -> bool) -> (usize, usize) {
+        use Ordering::Relaxed;
+
+        let mut it = self.first_ptr.load(Relaxed);
+        let mut prev: *const Node = ptr::null();
+
+        let mut cnt = (0, 0);
+
+        while !it.is_null() {
+            let node = unsafe { &* (it as *mut Node as *const Node) };
+            let next = node.get_next_ptr().load(Relaxed);
+
+            cnt.0 += 1;
+            if f(node) {
+                cnt.1 += 1;
+
+                let link = if prev.is_null() {
+                    &self.first_ptr
+                } else {
+                    unsafe { (*prev).get_next_ptr() }
+                };
+                link.store(next, Relaxed);
+            } else {
+                prev = node;
+            }
+            it = next;
+        }
+
+        cnt.0 -= cnt.1;
+
+        cnt
+    }
+
+    pub fn clear(&mut self) {
+        self.first_ptr.store(ptr::null_mut(), Ordering::Relaxed);
+    }
+}
+
+impl<'a, 'b, Node: IntrusiveForwardListNode<'a>>
+    IntoIterator for &'b IntrusiveForwardList<'a, Node>
+{
+    type Item = &'a Node;
+    type IntoIter = IntrusiveForwardListIterator<'a, 'b, Node>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct IntrusiveForwardListIterator<'a, 'b, Node: IntrusiveForwardListNode<'a>> {
+    curr_ptr: *mut (),
+    phantom0: PhantomData<&'a Node>,
+    phantom1: PhantomData<&'b ()>,
+}
+
+impl<'a, 'b, Node: IntrusiveForwardListNode<'a>>
+    Iterator for IntrusiveForwardListIterator<'a, 'b, Node>
+{
+    type Item = &'a Node;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.curr_ptr.is_null() {
+            return None;
+        }
+
+        let curr_node = unsafe { &* (self.curr_ptr as *mut Node as *const Node) };
+        self.curr_ptr = curr_node.get_next_ptr().load(Ordering::Relaxed);
+
+        Some(curr_node)
+    }
+}
+
+#[cfg(all(test, feature = "std", feature = "concurrency_toolkit"))]
+mod tests {
+    use super::*;
+
+    type Node<T = usize> = IntrusiveForwardListNodeImpl<T>;
+
+    fn setup() -> Vec<Node> {
+        (0..100).map(Node::new).collect()
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_forward_list_push_front() {
+        let nodes = setup();
+
+        let list = IntrusiveForwardList::new();
+        assert!(list.is_empty());
+        assert_eq!("[]", format!("{:?}", list));
+
+        for node in &nodes {
+            unsafe { list.push_front(node) };
+            assert!(!list.is_empty());
+        }
+
+        for (index, node) in (0..100).rev().zip(&list) {
+            assert_eq!(index, *node.get_elem());
+        }
+        assert_eq!(100, list.iter().count());
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_forward_list_remove_if() {
+        let nodes = setup();
+
+        let mut list = IntrusiveForwardList::new();
+        for node in &nodes {
+            unsafe { list.push_front(node) };
+        }
+
+        assert_eq!((50, 50), list.remove_if(|node| *node.get_elem() % 2 == 0));
+        for (index, node) in (1..100).rev().step_by(2).zip(&list) {
+            assert_eq!(index, *node.get_elem());
+        }
+        assert_eq!(50, list.iter().count());
+
+        list.clear();
+        assert!(list.is_empty());
+    }
+}
@@ -1,12 +1,26 @@
 use core::marker::PhantomData;
 use core::ptr;
-use core::iter::{Iterator, IntoIterator, DoubleEndedIterator};
+use core::mem;
+use core::cell::UnsafeCell;
+use core::iter::{self, Iterator, IntoIterator, DoubleEndedIterator};
 use core::convert::From;
+use core::array;
+use core::cmp;
 use core::fmt::{self, Debug, Formatter};
+#[cfg(feature = "std")]
+use core::hash::Hash;
 
-use concurrency_toolkit::atomic::{AtomicPtr, Ordering};
+#[cfg(feature = "alloc")]
+use alloc::{vec::Vec, collections::{BTreeMap, BTreeSet}};
+#[cfg(feature = "std")]
+use std::collections::{HashMap, HashSet};
+
+use crate::utility::atomic::{AtomicPtr, Ordering};
+#[cfg(feature = "counter")]
+use crate::utility::atomic::AtomicUsize;
 
 use crate::utility::*;
+pub use crate::utility::{OrderingPolicy, DefaultOrdering};
 use crate::intrusive_forward_list::IntrusiveForwardListNode;
 
 /// Doubly linked intrusive list node.
@@ -64,820 +78,4868 @@ unsafe impl<'a, T: 'a> IntrusiveListNode<'a> for IntrusiveListNodeImpl<T> {
     }
 }
 
+/// Intrusive list node whose element can be mutated through a shared reference.
+///
+/// # Safety
+///
+/// `get_elem_mut` must return a pointer to the element that is valid for writes,
+/// e.g. by storing the element inside an `UnsafeCell`.
+pub unsafe trait IntrusiveListNodeMut<'a>: IntrusiveListNode<'a> {
+    type Elem;
+
+    /// # Safety
+    ///
+    /// There must be no other reference to the element while the returned
+    /// reference is alive.
+    #[allow(clippy::mut_from_ref)]
+    unsafe fn get_elem_mut(&self) -> &mut Self::Elem;
+
+    /// Swap elements of `a` and `b` without moving the nodes.
+    ///
+    /// # Safety
+    ///
+    /// There must be no other reference to the elements of `a` and `b`,
+    /// e.g. no reader of the list they are in.
+    unsafe fn swap_elems(a: &Self, b: &Self) where Self: Sized {
+        if !ptr::eq(a, b) {
+            mem::swap(a.get_elem_mut(), b.get_elem_mut());
+        }
+    }
+}
+unsafe impl<'a, T: 'a> IntrusiveListNodeMut<'a> for IntrusiveListNodeImpl<UnsafeCell<T>> {
+    type Elem = T;
+
+    #[allow(clippy::mut_from_ref)]
+    unsafe fn get_elem_mut(&self) -> &mut Self::Elem {
+        &mut *self.elem.get()
+    }
+}
+
+/// Intrusive list node that has a key associated with its element.
+pub trait KeyedNode<'a>: IntrusiveListNode<'a> {
+    type Key;
+
+    fn get_key(&self) -> &Self::Key;
+}
+
+/// Intrusive list node that records the list it is added to.
+///
+/// `get_list_tag` is expected to store the address of the `IntrusiveList`
+/// containing the node, which is maintained by the user.
+pub trait TaggedNode<'a>: IntrusiveListNode<'a> {
+    fn get_list_tag(&self) -> &AtomicPtr<()>;
+}
+
+/// Maximal # num of elements printed by `Debug` impls, so that a corrupted
+/// list does not loop forever.
+const DEBUG_MAX_ELEMS: usize = 4096;
+
+fn debug_elems<'a, Node: IntrusiveListNode<'a>, P: OrderingPolicy>(
+    fmt: &mut Formatter<'_>,
+    mut iter: IntrusiveListIterator<'a, '_, Node, P>
+) -> fmt::Result
+where
+    Node::Target: Debug,
+{
+    let mut list = fmt.debug_list();
+    list.entries(iter.by_ref().take(DEBUG_MAX_ELEMS).map(|node| node.get_elem()));
+
+    if iter.next().is_some() {
+        list.finish_non_exhaustive()
+    } else {
+        list.finish()
+    }
+}
+
 /// IntrusiveList guarantees that
 ///  - push and read can be done concurrently while allowing stale read;
 ///  - deletion can only be done sequentially when there is no
 ///    writer (excluding the thread doing deletion) or reader.
 /// 
 /// It is suggested to use this with `RwLock`
-pub struct IntrusiveList<'a, Node: IntrusiveListNode<'a>> {
-    first_ptr: AtomicPtr<()>,
-    last_ptr: AtomicPtr<()>,
+///
+/// # Aliasing and provenance
+///
+/// Links are stored as `*mut ()` obtained by casting `&'a Node`, which keeps
+/// the provenance of the reference, and are only ever turned back into
+/// `&'a Node`, never `&mut Node`, nor through integers.
+///
+/// Thus the `unsafe` code relies on:
+///  - nodes outliving the list, which is guaranteed by `'a`;
+///  - nodes only being mutated through their links, which are atomics,
+///    or through `IntrusiveListNodeMut::get_elem_mut`, which requires
+///    the element to be in an `UnsafeCell`.
+///
+/// `run_tests.sh` checks this by running `test_list_provenance` under Miri
+/// with `-Zmiri-strict-provenance`, using both Stacked Borrows and
+/// Tree Borrows.
+pub struct IntrusiveList<'a, Node: IntrusiveListNode<'a>, P: OrderingPolicy = DefaultOrdering> {
+    first_ptr: EndPtr,
+    last_ptr: EndPtr,
+    #[cfg(feature = "counter")]
+    len: AtomicUsize,
     phantom: PhantomData<&'a Node>,
+    policy: PhantomData<P>,
 }
-impl<'a, Node: IntrusiveListNode<'a> + Debug> Debug for IntrusiveList<'a, Node> {
+impl<'a, Node: IntrusiveListNode<'a>, P: OrderingPolicy> Debug for IntrusiveList<'a, Node, P>
+where
+    Node::Target: Debug,
+{
     fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
-        fmt.debug_list().entries(self).finish()
+        debug_elems(fmt, self.iter())
     }
 }
-impl<'a, Node: IntrusiveListNode<'a>> Default for IntrusiveList<'a, Node> {
+impl<'a, Node: IntrusiveListNode<'a>, P: OrderingPolicy> Default for IntrusiveList<'a, Node, P> {
     fn default() -> Self {
-        Self::new()
+        Self::with_policy()
+    }
+}
+/// Serialize elements of the list as a sequence.
+///
+/// It only reads the list, so it can be done concurrently with pushes.
+#[cfg(feature = "serde")]
+impl<'a, Node: IntrusiveListNode<'a>, P: OrderingPolicy> serde::Serialize for IntrusiveList<'a, Node, P>
+where
+    Node::Target: serde::Serialize,
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter().map(|node| node.get_elem()))
     }
 }
 impl<'a, Node: IntrusiveListNode<'a>> IntrusiveList<'a, Node> {
     pub fn new() -> Self {
+        Self::with_policy()
+    }
+
+    /// Create a new list containing `nodes` in order.
+    ///
+    /// # Safety
+    ///
+    ///  * `nodes` -  __**YOU MUST NOT USE THEM IN OTHER LISTS/SPLICES SIMULTANEOUSLY
+    ///    OR ADD THE SAME NODE MORE THAN ONCE**__.
+    pub unsafe fn from_nodes(nodes: impl IntoIterator<Item = &'a Node>) -> Self {
+        let list = Self::new();
+        for node in nodes {
+            list.push_back(node);
+        }
+        list
+    }
+}
+impl<'a, Node: IntrusiveListNode<'a>, P: OrderingPolicy> IntrusiveList<'a, Node, P> {
+    /// Create an empty list that uses memory orderings specified by `P`.
+    pub fn with_policy() -> Self {
         Self {
-            first_ptr: AtomicPtr::new(ptr::null_mut()),
-            last_ptr: AtomicPtr::new(ptr::null_mut()),
+            first_ptr: new_end_ptr(),
+            last_ptr: new_end_ptr(),
+            #[cfg(feature = "counter")]
+            len: AtomicUsize::new(0),
             phantom: PhantomData,
+            policy: PhantomData,
         }
     }
 
+    /// In debug builds, panics if `node` is obviously still linked,
+    /// e.g. it is pushed twice.
+    ///
     /// # Safety
     ///
     ///  * `node` -  __**YOU MUST NOT USE IT IN OTHER LISTS/SPLICES SIMULTANEOUSLY OR
     ///    ADD IT TO THE SAME LIST SIMULTANEOUSLY
     ///    but you can REMOVE IT FROM THE SAME LIST SIMULTANEOUSLY**__.
     pub unsafe fn push_back(&self, node: &'a Node) {
+        self.debug_assert_unlinked(node);
         self.push_back_splice(Splice::new_unchecked(node, node));
     }
 
+    /// In debug builds, panics if `node` is obviously still linked,
+    /// e.g. it is pushed twice.
+    ///
     /// # Safety
     ///
     ///  * `node` -  __**YOU MUST NOT USE IT IN OTHER LISTS/SPLICES SIMULTANEOUSLY OR
     ///    ADD IT TO THE SAME LIST SIMULTANEOUSLY
     ///    but you can REMOVE IT FROM THE SAME LIST SIMULTANEOUSLY**__.
     pub unsafe fn push_front(&self, node: &'a Node) {
+        self.debug_assert_unlinked(node);
         self.push_front_splice(Splice::new_unchecked(node, node));
     }
 
+    /// Catch the common misuse of pushing a node that is still linked,
+    /// e.g. pushing the same node twice, in debug builds.
+    ///
+    /// Nodes removed from a list have their links reset to null, so they
+    /// can be pushed again.
+    fn debug_assert_unlinked(&self, node: &'a Node) {
+        use Ordering::Relaxed;
+
+        debug_assert!(
+            node.get_next_ptr().load(Relaxed).is_null() &&
+                node.get_prev_ptr().load(Relaxed).is_null() &&
+                !ptr::eq(self.first_ptr.load(Relaxed), node as *const _ as *mut ()),
+            "node is pushed while still being linked, e.g. pushed twice"
+        );
+    }
+
+    /// Atomically link the whole `splice` to the back of `self`.
+    ///
+    /// Does nothing if `splice` is empty.
     pub fn push_back_splice(&self, splice: Splice<'a, Node>) {
+        #[cfg(feature = "counter")]
+        let cnt = splice.count_nodes();
+
+        self.link_back_splice(splice);
+
+        #[cfg(feature = "counter")]
+        self.len.fetch_add(cnt, P::W_ORD);
+    }
+
+    /// `push_back_splice` without updating the counter.
+    fn link_back_splice(&self, splice: Splice<'a, Node>) {
+        if splice.is_empty() {
+            return;
+        }
+
         let null = ptr::null_mut();
 
         let last_node  = unsafe { &*(splice.last_ptr  as *mut Node as *const Node) };
         let first_node = unsafe { &*(splice.first_ptr as *mut Node as *const Node) };
 
-        last_node.get_next_ptr().store(null, W_ORD);
+        last_node.get_next_ptr().store(null, P::W_ORD);
 
         loop {
-            let last = self.last_ptr.load(R_ORD);
+            let last = self.last_ptr.load(P::R_ORD);
 
-            first_node.get_prev_ptr().store(last, W_ORD);
+            first_node.get_prev_ptr().store(last, P::W_ORD);
 
             let first_node = splice.first_ptr;
             if last.is_null() {
                 match self.first_ptr
-                    .compare_exchange_weak(null, first_node, RW_ORD, R_ORD)
+                    .compare_exchange_weak(null, first_node, P::RW_ORD, P::R_ORD)
                 {
                     Ok(_) => (),
-                    Err(_) => continue,
+                    Err(_) => {
+                        spin_wait();
+                        continue
+                    },
                 }
             } else {
                 match unsafe { &*(last as *mut Node as *const Node) }
                     .get_next_ptr()
-                    .compare_exchange_weak(null, first_node, RW_ORD, R_ORD)
+                    .compare_exchange_weak(null, first_node, P::RW_ORD, P::R_ORD)
                 {
                     Ok(_) => (),
-                    Err(_) => continue,
+                    Err(_) => {
+                        spin_wait();
+                        continue
+                    },
                 }
             }
             let last_node = splice.last_ptr;
-            break assert_store_ptr(&self.last_ptr, last, last_node);
+            break assert_store_ptr::<P, _>(&self.last_ptr, last, last_node);
         }
     }
 
+    /// Atomically link the whole `splice` to the front of `self`.
+    ///
+    /// Does nothing if `splice` is empty.
     pub fn push_front_splice(&self, splice: Splice<'a, Node>) {
+        if splice.is_empty() {
+            return;
+        }
+
+        #[cfg(feature = "counter")]
+        let cnt = splice.count_nodes();
+
         let null = ptr::null_mut();
 
         let last_node  = unsafe { &*(splice.last_ptr  as *mut Node as *const Node) };
         let first_node = unsafe { &*(splice.first_ptr as *mut Node as *const Node) };
 
-        first_node.get_prev_ptr().store(null, W_ORD);
+        first_node.get_prev_ptr().store(null, P::W_ORD);
 
         loop {
-            let first = self.first_ptr.load(R_ORD);
+            let first = self.first_ptr.load(P::R_ORD);
 
-            last_node.get_next_ptr().store(first, W_ORD);
+            last_node.get_next_ptr().store(first, P::W_ORD);
 
             let last_node  = splice.last_ptr;
             let first_node = splice.first_ptr;
 
             if first.is_null() {
                 match self.first_ptr
-                    .compare_exchange_weak(null, first_node, RW_ORD, R_ORD)
+                    .compare_exchange_weak(null, first_node, P::RW_ORD, P::R_ORD)
                 {
-                    Ok(_) => break assert_store_ptr(&self.last_ptr, null, last_node),
-                    Err(_) => continue,
+                    Ok(_) => break assert_store_ptr::<P, _>(&self.last_ptr, null, last_node),
+                    Err(_) => {
+                        spin_wait();
+                        continue
+                    },
                 }
             } else {
                 match unsafe { &*(first as *mut Node as *const Node) }
                     .get_prev_ptr()
-                    .compare_exchange_weak(null, last_node, RW_ORD, R_ORD)
+                    .compare_exchange_weak(null, last_node, P::RW_ORD, P::R_ORD)
                 {
-                    Ok(_) => break assert_store_ptr(&self.first_ptr, first, first_node),
-                    Err(_) => continue,
+                    Ok(_) => break assert_store_ptr::<P, _>(&self.first_ptr, first, first_node),
+                    Err(_) => {
+                        spin_wait();
+                        continue
+                    },
                 }
             }
         }
-    }
-
-    // All methods read the list:
-
-    pub fn iter(&self) -> IntrusiveListIterator<'a, '_, Node> {
-        IntrusiveListIterator::from_list(self)
-    }
 
-    pub fn is_empty(&self) -> bool {
-        self.first_ptr.load(R_ORD).is_null() && self.last_ptr.load(R_ORD).is_null()
+        #[cfg(feature = "counter")]
+        self.len.fetch_add(cnt, P::W_ORD);
     }
 
-    // All methods below are removal methods, which takes the write lock:
-
-    /// Returns `true` if `node` is indeed inside `self`, otherwise `false`.
+    /// Link `new` right after `existing`.
     ///
     /// # Safety
     ///
-    ///  * `node` - it must be in one of the following state:
-    ///     - `node.get_next_ptr().is_null() && node.get_prev_ptr().is_null()`
-    ///     - `node` is added to `self`
-    ///    and, __**YOU MUST NOT USE IT IN OTHER LISTS/SPLICES SIMULTANEOUSLY OR
+    ///  * `existing` - must be in `self` and must not be removed simultaneously.
+    ///    It also must not be the node before `existing` passed to
+    ///    `insert_before` simultaneously.
+    ///  * `new` -  __**YOU MUST NOT USE IT IN OTHER LISTS/SPLICES SIMULTANEOUSLY OR
     ///    ADD IT TO THE SAME LIST SIMULTANEOUSLY
     ///    but you can REMOVE IT FROM THE SAME LIST SIMULTANEOUSLY**__.
-    pub unsafe fn remove_node(&mut self, node: &'a Node) -> bool {
-        self.splice_impl(node, node).is_some()
-    }
-
-    ///  * `f` - return true to remove the node or false to keep it
-    /// 
-    /// Return (# num of elements left, # num of elements removed)
-    pub fn remove_if(&mut self, mut f: impl FnMut(&'a Node) -> bool) -> (usize, usize) {
-        use Ordering::Relaxed;
+    pub unsafe fn insert_after(&self, existing: &'a Node, new: &'a Node) {
+        let existing_ptr = existing as *const _ as *mut ();
+        let new_ptr = new as *const _ as *mut ();
 
-        let mut it = self.first_ptr.load(Relaxed);
+        new.get_prev_ptr().store(existing_ptr, P::W_ORD);
 
-        let mut prev: *const Node = ptr::null();
-        let mut beg: *const Node = ptr::null();
+        let next = loop {
+            let next = existing.get_next_ptr().load(P::R_ORD);
 
-        let mut cnt = (0, 0);
+            new.get_next_ptr().store(next, P::W_ORD);
 
-        while !it.is_null() {
-            let node = unsafe { &* (it as *mut Node as *const Node) };
-            cnt.0 += 1;
-            if f(node) {
-                cnt.1 += 1;
-                if beg.is_null() {
-                    beg = node;
-                }
-            } else if !beg.is_null() {
-                unsafe { self.splice_impl(&* beg, &* prev).unwrap() };
-                beg = ptr::null();
+            match existing.get_next_ptr()
+                .compare_exchange_weak(next, new_ptr, P::RW_ORD, P::R_ORD)
+            {
+                Ok(_) => break next,
+                Err(_) => {
+                    spin_wait();
+                    continue
+                },
             }
-            prev = node;
-            it = node.get_next_ptr().load(Relaxed);
-        }
-
-        cnt.0 -= cnt.1;
-
-        cnt
-    }
-
-    pub fn clear(&mut self) {
-        use Ordering::Relaxed;
+        };
 
-        let null = ptr::null_mut();
+        if next.is_null() {
+            assert_store_ptr::<P, _>(&self.last_ptr, existing_ptr, new_ptr);
+        } else {
+            (*(next as *mut Node)).get_prev_ptr().store(new_ptr, P::W_ORD);
+        }
 
-        self.first_ptr.store(null, Relaxed);
-        self.last_ptr .store(null, Relaxed);
+        #[cfg(feature = "counter")]
+        self.len.fetch_add(1, P::W_ORD);
     }
 
-    /// Move all list nodes between `first` and `last` (inclusive) from `self`
-    /// and return `Some(())`.
-    ///
-    /// Or return `None` if `first` or `last` does not belong to `self`.
+    /// Link `new` right before `existing`.
     ///
     /// # Safety
     ///
-    ///  * `first`, `last` - `first` must be to the left of the `last` 
-    ///    (`first` can be the same node as `last`) and
-    ///    __**YOU MUST NOT USE IT IN OTHER LISTS/SPLICES SIMULTANEOUSLY OR
+    ///  * `existing` - must be in `self` and must not be removed simultaneously.
+    ///    It also must not be the node after `existing` passed to
+    ///    `insert_after` simultaneously.
+    ///  * `new` -  __**YOU MUST NOT USE IT IN OTHER LISTS/SPLICES SIMULTANEOUSLY OR
     ///    ADD IT TO THE SAME LIST SIMULTANEOUSLY
     ///    but you can REMOVE IT FROM THE SAME LIST SIMULTANEOUSLY**__.
-    ///
-    /// Must be called after obtained a write lock of `self.rwlock`.
-    #[must_use]
-    unsafe fn splice_impl(&mut self, first: &'a Node, last: &'a Node) -> Option<()> {
-        use Ordering::Relaxed;
+    pub unsafe fn insert_before(&self, existing: &'a Node, new: &'a Node) {
+        let existing_ptr = existing as *const _ as *mut ();
+        let new_ptr = new as *const _ as *mut ();
 
-        let prev_node = first.get_prev_ptr().load(Relaxed);
-        let next_node = last .get_next_ptr().load(Relaxed);
+        new.get_next_ptr().store(existing_ptr, P::W_ORD);
 
-        let last_ptr = if next_node.is_null() {
-            &self.last_ptr
-        } else {
-            let next_node = next_node as *mut Node;
-            (*next_node).get_prev_ptr()
-        };
-        let last = last as *const _ as *mut ();
-        match last_ptr.compare_exchange_weak(last, prev_node, Relaxed, Relaxed) {
-            Ok(_) => (),
-            Err(_) => return None,
-        }
+        let prev = loop {
+            let prev = existing.get_prev_ptr().load(P::R_ORD);
 
-        let first_ptr = if prev_node.is_null() {
-            &self.first_ptr
-        } else {
-            let prev_node = prev_node as *mut Node;
-            (*prev_node).get_next_ptr()
-        };
-        let first = first as *const _ as *mut ();
-        if ptr::eq(first, last) {
-            assert_store_ptr_relaxed(first_ptr, first, next_node);
-        } else {
-            match first_ptr.compare_exchange_weak(first, next_node, Relaxed, Relaxed) {
-                Ok(_) => (),
+            new.get_prev_ptr().store(prev, P::W_ORD);
+
+            match existing.get_prev_ptr()
+                .compare_exchange_weak(prev, new_ptr, P::RW_ORD, P::R_ORD)
+            {
+                Ok(_) => break prev,
                 Err(_) => {
-                    // Revert the change of last_ptr
-                    assert_store_ptr_relaxed(last_ptr, prev_node, last);
-                    return None
+                    spin_wait();
+                    continue
                 },
             }
+        };
+
+        if prev.is_null() {
+            assert_store_ptr::<P, _>(&self.first_ptr, existing_ptr, new_ptr);
+        } else {
+            (*(prev as *mut Node)).get_next_ptr().store(new_ptr, P::W_ORD);
         }
 
-        Some(())
+        #[cfg(feature = "counter")]
+        self.len.fetch_add(1, P::W_ORD);
     }
 
-    /// Move all list nodes between `first` and `last` (inclusive) from `self`
-    /// and return them as `Some(Splice)`.
+    /// Link all `nodes` to the back of `self` in order.
     ///
-    /// Or return `None` if `first` or `last` does not belong to `self`.
+    /// Nodes are chained together before being linked to `self` at once, so
+    /// readers either see all of them or none of them.
     ///
     /// # Safety
     ///
-    ///  * `first`, `last` - `first` must be to the left of the `last` and
-    ///    __**YOU MUST NOT USE IT IN OTHER LISTS/SPLICES SIMULTANEOUSLY OR
-    ///    ADD IT TO THE SAME LIST SIMULTANEOUSLY
-    ///    but you can REMOVE IT FROM THE SAME LIST SIMULTANEOUSLY**__.
-    #[must_use]
-    pub unsafe fn splice(
-        &mut self,
-        first: &'a Node,
-        last: &'a Node
-    ) -> Option<Splice<'a, Node>> {
-        self.splice_impl(first, last).map(|_| {Splice::new_unchecked(first, last)})
+    ///  * `nodes` -  __**YOU MUST NOT USE THEM IN OTHER LISTS/SPLICES SIMULTANEOUSLY
+    ///    OR ADD THE SAME NODE MORE THAN ONCE**__.
+    pub unsafe fn extend_back(&self, nodes: impl IntoIterator<Item = &'a Node>) {
+        let mut splice = Splice::new_empty();
+        for node in nodes {
+            splice.push_back(node);
+        }
+        self.push_back_splice(splice);
     }
-}
 
-/// `Splice` can be used to
-///  - move list nodes between `IntrusiveList` efficiently;
-///  - insert/remove list nodes from one `IntrusiveList` efficiently;
-///  - iterate over list and remove nodes without starving the rest of readers/removers
-///    waiting on `IntrusiveList`.
-pub struct Splice<'a, Node: IntrusiveListNode<'a>> {
-    first_ptr: * mut (),
-    last_ptr: *mut (),
-    phantom: PhantomData<&'a Node>,
-}
-unsafe impl<'a, Node: IntrusiveListNode<'a> + Debug> Send for Splice<'a, Node> {}
-impl<'a, Node: IntrusiveListNode<'a> + Debug> Debug for Splice<'a, Node> {
-    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
-        fmt.debug_list().entries(self).finish()
-    }
-}
-impl<'a, Node: IntrusiveListNode<'a>> Default for Splice<'a, Node> {
-    fn default() -> Self {
-        Self::new_empty()
-    }
-}
-impl<'a, Node: IntrusiveListNode<'a>> Splice<'a, Node> {
-    /// # Safety
-    ///
-    /// Assumes `first` and `last` is already linked, `first` must be to the
-    /// left of the `last` (`first` and `last` can be the same node)
-    /// and and the link must not be modified after `Splice` is created.
-    ///
-    /// Also, __**YOU MUST NOT USE IT IN OTHER LISTS/SPLICES SIMULTANEOUSLY OR
-    /// ADD IT TO THE SAME LIST SIMULTANEOUSLY
-    /// but you can REMOVE IT FROM THE SAME LIST SIMULTANEOUSLY**__.
-    pub unsafe fn new_unchecked(first: &'a Node, last: &'a Node) -> Self {
-        Self {
-            first_ptr: first as *const _ as *mut (),
-            last_ptr:  last  as *const _ as *mut (),
-            phantom: PhantomData,
-        }
+    fn from_splice(splice: Splice<'a, Node>) -> Self {
+        let list = Self::with_policy();
+        list.push_back_splice(splice);
+        list
     }
 
-    pub fn new_empty() -> Self {
-        let null = ptr::null_mut();
-        Self {
-            first_ptr: null,
-            last_ptr:  null,
-            phantom: PhantomData,
-        }
+    // All methods read the list:
+
+    /// Return a double-ended iterator over the nodes of `self`.
+    ///
+    /// The iterator takes a snapshot of the first and last node on creation,
+    /// so it might observe a stale view of `self` and nodes pushed after
+    /// creation of the iterator are not yielded.
+    pub fn iter(&self) -> IntrusiveListIterator<'a, '_, Node, P> {
+        IntrusiveListIterator::from_list(self)
     }
 
     pub fn is_empty(&self) -> bool {
-        self.first_ptr.is_null()
+        self.first_ptr.load(P::R_ORD).is_null() && self.last_ptr.load(P::R_ORD).is_null()
     }
 
-    /// # Safety
+    /// Return # num of nodes in `self`.
     ///
-    ///  * `node` -  __**YOU MUST NOT USE IT IN OTHER LISTS/SPLICES SIMULTANEOUSLY OR
-    ///    ADD IT TO THE SAME LIST SIMULTANEOUSLY
-    ///    but you can REMOVE IT FROM THE SAME LIST SIMULTANEOUSLY**__.
-    pub unsafe fn push_front(&mut self, node: &'a Node) {
-        self.push_front_splice(Splice::new_unchecked(node, node))
+    /// If there are concurrent pushes, the value returned is only a
+    /// snapshot at the point of calling.
+    #[cfg(feature = "counter")]
+    pub fn len(&self) -> usize {
+        self.len.load(P::R_ORD)
     }
 
-    pub fn push_front_splice(&mut self, splice: Self) {
-        use Ordering::Relaxed;
+    /// Return the first and last node of the longest run in which every
+    /// pair of adjacent nodes satisfies `less(prev, next)`.
+    ///
+    /// If there are multiple longest runs, the first one is returned.
+    ///
+    /// Return `None` if `self` is empty.
+    pub fn longest_sorted_run(
+        &self,
+        less: impl Fn(&Node, &Node) -> bool
+    ) -> Option<(&'a Node, &'a Node)> {
+        let mut iter = self.iter();
+
+        let first = iter.next()?;
+
+        let mut longest = (first, first, 1);
+        let mut curr = (first, 1);
+        let mut prev = first;
+
+        for node in iter {
+            if less(prev, node) {
+                curr.1 += 1;
+            } else {
+                curr = (node, 1);
+            }
 
-        let last_node = unsafe { &*(splice.last_ptr as *mut Node as *const Node) };
+            if curr.1 > longest.2 {
+                longest = (curr.0, node, curr.1);
+            }
+            prev = node;
+        }
 
-        let first = self.first_ptr;
+        Some((longest.0, longest.1))
+    }
 
-        last_node.get_next_ptr().store(first, Relaxed);
+    /// Return the nodes in `self` sorted by their addresses.
+    ///
+    /// Useful for producing output that does not depend on the order of
+    /// the list.
+    #[cfg(feature = "alloc")]
+    pub fn iter_by_address(&self) -> impl Iterator<Item = &'a Node> {
+        let mut nodes: Vec<&'a Node> = self.iter().collect();
+        nodes.sort_unstable_by_key(|node| *node as *const Node);
+        nodes.into_iter()
+    }
 
-        self.first_ptr = splice.first_ptr;
-        if first.is_null() {
-            self.last_ptr = splice.last_ptr;
-        } else {
-            let first = unsafe { &*(first as *mut Node as *const Node) };
-            first.get_prev_ptr().store(splice.last_ptr, Relaxed);
+    /// Return `true` if no node in `self` is `less` than the node before it.
+    ///
+    /// Empty list and list with only one node are always sorted.
+    pub fn is_sorted_by(&self, less: impl Fn(&Node, &Node) -> bool) -> bool {
+        let mut iter = self.iter();
+
+        let mut prev = match iter.next() {
+            Some(node) => node,
+            None => return true,
+        };
+
+        for node in iter {
+            if less(node, prev) {
+                return false;
+            }
+            prev = node;
         }
+
+        true
     }
 
-    /// # Safety
-    ///
-    ///  * `node` -  __**YOU MUST NOT USE IT IN OTHER LISTS/SPLICES SIMULTANEOUSLY OR
-    ///    ADD IT TO THE SAME LIST SIMULTANEOUSLY
-    ///    but you can REMOVE IT FROM THE SAME LIST SIMULTANEOUSLY**__.
-    pub unsafe fn push_back(&mut self, node: &'a Node) {
-        self.push_back_splice(Splice::new_unchecked(node, node))
+    /// Return nodes in `self` whose element has not appeared in any of
+    /// the nodes before it.
+    #[cfg(feature = "std")]
+    pub fn iter_unique(&self) -> impl Iterator<Item = &'a Node> + '_
+    where
+        Node::Target: Eq + Hash,
+    {
+        let mut seen = HashSet::new();
+        self.iter().filter(move |node| seen.insert(node.get_elem()))
     }
 
-    pub fn push_back_splice(&mut self, splice: Self) {
-        use Ordering::Relaxed;
+    /// Return the node with the minimum element.
+    ///
+    /// If there are multiple minimum nodes, the first one is returned.
+    pub fn min_elem_node(&self) -> Option<&'a Node>
+    where
+        Node::Target: Ord,
+    {
+        self.iter().reduce(|min, node| {
+            if node.get_elem() < min.get_elem() { node } else { min }
+        })
+    }
 
-        let first_node = unsafe { &*(splice.first_ptr as *mut Node as *const Node) };
+    /// Return the node with the maximum element.
+    ///
+    /// If there are multiple maximum nodes, the first one is returned.
+    pub fn max_elem_node(&self) -> Option<&'a Node>
+    where
+        Node::Target: Ord,
+    {
+        self.iter().reduce(|max, node| {
+            if node.get_elem() > max.get_elem() { node } else { max }
+        })
+    }
 
-        let last = self.last_ptr;
+    /// Return each node in `self` along with the element of the node after it.
+    ///
+    /// The element paired with the last node is `None`.
+    pub fn iter_with_next_elem(
+        &self
+    ) -> impl Iterator<Item = (&'a Node, Option<Node::Target>)> + '_ {
+        let mut iter = self.iter().peekable();
+
+        iter::from_fn(move || {
+            let node = iter.next()?;
+            Some((node, iter.peek().map(|next| next.get_elem())))
+        })
+    }
 
-        first_node.get_prev_ptr().store(last, Relaxed);
+    /// Return the accumulated value after `f` is applied to each node,
+    /// starting from `init`.
+    #[cfg(feature = "alloc")]
+    pub fn scan_elems<B: Clone>(&self, init: B, mut f: impl FnMut(&B, &Node) -> B) -> Vec<B> {
+        let mut acc = init;
+
+        self.iter()
+            .map(|node| {
+                acc = f(&acc, node);
+                acc.clone()
+            })
+            .collect()
+    }
 
-        self.last_ptr = splice.last_ptr;
-        if last.is_null() {
-            self.first_ptr = splice.first_ptr;
-        } else {
-            let last = unsafe { &*(last as *mut Node as *const Node) };
-            last.get_next_ptr().store(splice.first_ptr, Relaxed);
-        }
+    /// Return nodes in `self` that `f` returns true.
+    pub fn iter_filter<'b, F>(&'b self, f: F) -> impl Iterator<Item = &'a Node> + 'b
+    where
+        F: Fn(&Node) -> bool + 'b,
+    {
+        self.iter().filter(move |node| f(node))
     }
 
-    pub fn iter(&self) -> IntrusiveListIterator<'a, '_, Node> {
-        IntrusiveListIterator::from_splice(self)
+    /// Return elements of `self` from the last node to the first node.
+    #[cfg(feature = "alloc")]
+    pub fn snapshot_rev(&self) -> Vec<Node::Target> {
+        self.iter().rev().map(|node| node.get_elem()).collect()
     }
-}
-impl<'a, Node: IntrusiveListNode<'a>>
-    From<Splice<'a, Node>> for Option<(&'a Node, &'a Node)>
-{
-    /// If `splice` is empty, then return value will be `None`.
-    fn from(splice: Splice<'a, Node>) -> Self {
-        if splice.is_empty() {
-            None
-        } else {
-            Some(unsafe {(
-                &* (splice.first_ptr as *mut Node as *const Node),
-                &* (splice.last_ptr  as *mut Node as *const Node),
-            )})
-        }
+
+    /// Return the first node that `valid` returns false, or `None` if
+    /// `valid` returns true for every node.
+    pub fn find_invalid(&self, valid: impl Fn(&Node) -> bool) -> Option<&'a Node> {
+        self.iter().find(|node| !valid(node))
     }
-}
 
-impl<'a, 'b, Node: IntrusiveListNode<'a>> IntoIterator for &'b Splice<'a, Node> {
-    type Item = &'a Node;
-    type IntoIter = IntrusiveListIterator<'a, 'b, Node>;
+    /// Return `(node, prev, next)` pointers of each node in `self`.
+    ///
+    /// Useful for debugging corrupted links without requiring
+    /// `Node: Debug`.
+    #[cfg(feature = "alloc")]
+    pub fn iter_debug(&self) -> impl Iterator<Item = (*mut (), *mut (), *mut ())> {
+        use Ordering::Relaxed;
 
-    fn into_iter(self) -> Self::IntoIter {
-        Self::IntoIter::from_splice(self)
+        let links: Vec<_> = self.iter()
+            .map(|node| (
+                node as *const _ as *mut (),
+                node.get_prev_ptr().load(Relaxed),
+                node.get_next_ptr().load(Relaxed),
+            ))
+            .collect();
+        links.into_iter()
     }
-}
 
-impl<'a, 'b, Node: IntrusiveListNode<'a>> IntoIterator for &'b IntrusiveList<'a, Node> {
-    type Item = &'a Node;
-    type IntoIter = IntrusiveListIterator<'a, 'b, Node>;
+    /// Return `true` if `b` is the node right after `a`.
+    ///
+    /// # Safety
+    ///
+    ///  * `a`, `b` - must be in `self`.
+    pub unsafe fn are_adjacent(&self, a: &'a Node, b: &'a Node) -> bool {
+        ptr::eq(a.get_next_ptr().load(P::R_ORD), b as *const _ as *mut ())
+    }
 
-    fn into_iter(self) -> Self::IntoIter {
-        Self::IntoIter::from_list(self)
+    /// Group elements of `self` by the key returned by `key`.
+    ///
+    /// Elements in each group are in the same order as in `self`.
+    #[cfg(feature = "std")]
+    pub fn group_to_map<K: Eq + Hash>(
+        &self,
+        key: impl Fn(&Node) -> K
+    ) -> HashMap<K, Vec<Node::Target>> {
+        let mut map: HashMap<_, Vec<_>> = HashMap::new();
+        for node in self {
+            map.entry(key(node)).or_default().push(node.get_elem());
+        }
+        map
     }
-}
 
-#[derive(Copy, Clone, Debug)]
-pub struct IntrusiveListIterator<'a, 'b, Node: IntrusiveListNode<'a>> {
-    first_ptr: * mut (),
-    last_ptr: *mut (),
-    phantom0: PhantomData<&'a Node>,
-    phantom1: PhantomData<&'b ()>,
-}
-impl<'a, 'b, Node: IntrusiveListNode<'a>> IntrusiveListIterator<'a, 'b, Node> {
-    pub(crate) fn from_list(list: &'b IntrusiveList<'a, Node>) -> Self {
+    /// Return the last node that `f` returns true.
+    pub fn rfind(&self, f: impl Fn(&Node) -> bool) -> Option<&'a Node> {
+        self.iter().rev().find(|node| f(node))
+    }
+
+    /// Return the index of `node` in `self`, or `None` if `node` is not in `self`.
+    ///
+    /// Search from both the first node and the last node simultaneously.
+    pub fn index_of(&self, node: &'a Node) -> Option<usize> {
+        let mut iter = self.iter();
+        let mut index = 0;
+
         loop {
-            let first_ptr = list.first_ptr.load(R_ORD);
-            let last_ptr  = list.last_ptr .load(R_ORD);
+            if ptr::eq(iter.next()?, node) {
+                break Some(index);
+            }
+            index += 1;
 
-            if (first_ptr.is_null() && last_ptr.is_null()) ||
-               ( (!first_ptr.is_null()) && (!last_ptr.is_null()) )
-            {
-                break Self {
-                    first_ptr,
-                    last_ptr,
-                    phantom0: PhantomData,
-                    phantom1: PhantomData,
-                }
+            if ptr::eq(iter.next_back()?, node) {
+                break Some(index + iter.count());
             }
         }
     }
 
-    pub(crate) fn from_splice(splice: &'b Splice<'a, Node>) -> Self {
-        Self {
-            first_ptr: splice.first_ptr,
-            last_ptr:  splice.last_ptr,
-            phantom0: PhantomData,
-            phantom1: PhantomData,
-        }
+    /// Return nodes from the start of `self` while the sum of their elements,
+    /// computed by `add`, is less than `limit`.
+    pub fn iter_until_sum<'b, F>(
+        &'b self,
+        limit: Node::Target,
+        add: F
+    ) -> impl Iterator<Item = &'a Node> + 'b
+    where
+        Node::Target: PartialOrd + 'b,
+        F: Fn(Node::Target, Node::Target) -> Node::Target + 'b,
+    {
+        let mut sum = None;
+
+        self.iter().map_while(move |node| {
+            let new_sum = match sum.take() {
+                Some(sum) => add(sum, node.get_elem()),
+                None => node.get_elem(),
+            };
+
+            if new_sum < limit {
+                sum = Some(new_sum);
+                Some(node)
+            } else {
+                None
+            }
+        })
     }
-}
 
-impl<'a, 'b, Node: IntrusiveListNode<'a>>
-    Iterator for IntrusiveListIterator<'a, 'b, Node>
-{
-    type Item = &'a Node;
+    /// Return elements of `self` as an array if `self` contains exactly `N`
+    /// nodes, otherwise `None`.
+    pub fn to_array<const N: usize>(&self) -> Option<[Node::Target; N]> {
+        let mut iter = self.iter();
+        let nodes: [Option<&'a Node>; N] = array::from_fn(|_| iter.next());
 
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.first_ptr.is_null() {
+        if nodes.iter().any(Option::is_none) || iter.next().is_some() {
             return None;
         }
+        Some(nodes.map(|node| node.unwrap().get_elem()))
+    }
 
-        let curr_node = unsafe { &* (self.first_ptr as *mut Node as *const Node) };
+    /// Return the index of the first element that differs between `self`
+    /// and `other`.
+    ///
+    /// Return `None` if they are equal up to the length of the shorter one.
+    pub fn first_divergence(&self, other: &IntrusiveList<'a, Node, P>) -> Option<usize>
+    where
+        Node::Target: PartialEq,
+    {
+        self.iter()
+            .zip(other)
+            .position(|(x, y)| x.get_elem() != y.get_elem())
+    }
 
-        if self.first_ptr == self.last_ptr {
-            self.first_ptr = ptr::null_mut();
-            self.last_ptr = self.first_ptr;
-        } else {
-            self.first_ptr = curr_node.get_next_ptr().load(Ordering::Relaxed);
+    /// Return elements of `self` along with their indexes.
+    #[cfg(feature = "alloc")]
+    pub fn enumerate_elems(&self) -> Vec<(usize, Node::Target)> {
+        self.iter().map(|node| node.get_elem()).enumerate().collect()
+    }
+
+    /// Return `true` if elements of `self` read the same forward and backward.
+    pub fn is_palindrome(&self) -> bool
+    where
+        Node::Target: PartialEq,
+    {
+        let mut iter = self.iter();
+
+        while let (Some(front), Some(back)) = (iter.next(), iter.next_back()) {
+            if front.get_elem() != back.get_elem() {
+                return false;
+            }
         }
 
-        Some(curr_node)
+        true
     }
 
-    fn last(self) -> Option<Self::Item> {
-        if self.last_ptr.is_null() {
-            None
-        } else {
-            Some(unsafe { &* (self.last_ptr as *mut Node as *const Node) })
+    /// Return nodes from `self` and `other` alternately, starting from `self`.
+    ///
+    /// Once one of them runs out of nodes, the rest of nodes of the other one
+    /// are returned.
+    pub fn round_robin_iter<'b>(
+        &'b self,
+        other: &'b IntrusiveList<'a, Node, P>
+    ) -> impl Iterator<Item = &'a Node> + 'b {
+        let mut iters = [self.iter(), other.iter()];
+        let mut index = 0;
+
+        iter::from_fn(move || {
+            let node = iters[index].next().or_else(|| iters[1 - index].next());
+            index = 1 - index;
+            node
+        })
+    }
+
+    /// Return the number of elements, the minimum element and the maximum
+    /// element of `self` in one pass.
+    pub fn stats(&self) -> ListStats<Node::Target>
+    where
+        Node::Target: Ord + Clone,
+    {
+        let mut stats = ListStats {
+            count: 0,
+            min: None,
+            max: None,
+        };
+
+        for node in self {
+            let elem = node.get_elem();
+
+            stats.count += 1;
+            match &stats.min {
+                Some(min) if *min <= elem => (),
+                _ => stats.min = Some(elem.clone()),
+            }
+            match &stats.max {
+                Some(max) if *max >= elem => (),
+                _ => stats.max = Some(elem),
+            }
         }
+
+        stats
     }
-}
-impl<'a, 'b, Node: IntrusiveListNode<'a>>
-    DoubleEndedIterator for IntrusiveListIterator<'a, 'b, Node>
-{
-    fn next_back(&mut self) -> Option<Self::Item> {
-        if self.last_ptr.is_null() {
-            return None;
+
+    /// Return nodes of `self` in the order of `perm`, which contains indexes
+    /// of nodes in `self`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any index in `perm` is out of range.
+    #[cfg(feature = "alloc")]
+    pub fn iter_permuted(&self, perm: &[usize]) -> impl Iterator<Item = &'a Node> {
+        let nodes: Vec<&'a Node> = self.iter().collect();
+        let permuted: Vec<&'a Node> = perm.iter().map(|index| nodes[*index]).collect();
+        permuted.into_iter()
+    }
+
+    /// Return the element of the node after the first node that `f` returns
+    /// true.
+    ///
+    /// Return `None` if no node matches or the matched node is the last node.
+    pub fn elem_after(&self, f: impl Fn(&Node) -> bool) -> Option<Node::Target> {
+        let mut iter = self.iter();
+        iter.find(|node| f(node))?;
+        iter.next().map(|node| node.get_elem())
+    }
+
+    /// Walk `self` from the first node via next pointers and from the last node
+    /// via prev pointers, and report whether the links are consistent.
+    ///
+    /// Each walk stops after `len()` nodes with feature `counter`, or after
+    /// `DEBUG_MAX_ELEMS` nodes otherwise, so that a cyclic or corrupted list
+    /// does not loop forever. If a walk is cut off, the links are reported as
+    /// inconsistent.
+    pub fn link_report(&self) -> LinkReport {
+        use Ordering::Relaxed;
+
+        #[cfg(feature = "counter")]
+        let max_len = self.len();
+        #[cfg(not(feature = "counter"))]
+        let max_len = DEBUG_MAX_ELEMS;
+
+        let first = self.first_ptr.load(P::R_ORD);
+        let last  = self.last_ptr .load(P::R_ORD);
+
+        let mut forward_len = 0;
+        let mut forward_end = ptr::null_mut();
+        let mut it = first;
+        while !it.is_null() && forward_len < max_len {
+            forward_len += 1;
+            forward_end = it;
+            it = unsafe { &* (it as *mut Node as *const Node) }.get_next_ptr().load(Relaxed);
+        }
+        let forward_cut_off = !it.is_null();
+
+        let mut backward_len = 0;
+        let mut backward_end = ptr::null_mut();
+        let mut it = last;
+        while !it.is_null() && backward_len < max_len {
+            backward_len += 1;
+            backward_end = it;
+            it = unsafe { &* (it as *mut Node as *const Node) }.get_prev_ptr().load(Relaxed);
         }
+        let backward_cut_off = !it.is_null();
+
+        LinkReport {
+            forward_len,
+            backward_len,
+            consistent: !forward_cut_off && !backward_cut_off &&
+                forward_len == backward_len &&
+                forward_end == last && backward_end == first,
+        }
+    }
 
-        let curr_node = unsafe { &* (self.last_ptr as *mut Node as *const Node) };
+    /// Return overlapping windows of `size` elements of `self`, like
+    /// `slice::windows`.
+    ///
+    /// Panics if `size` is 0.
+    #[cfg(feature = "alloc")]
+    pub fn windows_elems(&self, size: usize) -> impl Iterator<Item = Vec<Node::Target>> + '_ {
+        assert_ne!(size, 0, "window size must be non-zero");
 
-        if self.first_ptr == self.last_ptr {
-            self.first_ptr = ptr::null_mut();
-            self.last_ptr = self.first_ptr;
+        let mut iter = self.iter();
+        let mut window: Vec<&'a Node> = Vec::with_capacity(size);
+
+        iter::from_fn(move || {
+            if window.len() == size {
+                window.remove(0);
+            }
+            while window.len() < size {
+                window.push(iter.next()?);
+            }
+            Some(window.iter().map(|node| node.get_elem()).collect())
+        })
+    }
+
+    /// Return the first and last node of the first maximal run of consecutive
+    /// nodes that satisfy `pred` and contains at least `min_len` nodes.
+    ///
+    /// A `min_len` of 0 is treated as 1.
+    pub fn find_run(
+        &self,
+        pred: impl Fn(&Node) -> bool,
+        min_len: usize
+    ) -> Option<(&'a Node, &'a Node)> {
+        let min_len = cmp::max(min_len, 1);
+
+        let mut run: Option<(&'a Node, &'a Node)> = None;
+        let mut len = 0;
+
+        for node in self.iter() {
+            if pred(node) {
+                run = Some((run.map_or(node, |(first, _)| first), node));
+                len += 1;
+            } else {
+                if len >= min_len {
+                    return run;
+                }
+                run = None;
+                len = 0;
+            }
+        }
+
+        if len >= min_len {
+            run
         } else {
-            self.last_ptr = curr_node.get_prev_ptr().load(Ordering::Relaxed);
+            None
         }
+    }
 
-        Some(curr_node)
+    /// Return # num of occurrences of each distinct element in `self`.
+    #[cfg(feature = "std")]
+    pub fn histogram(&self) -> HashMap<Node::Target, usize>
+    where
+        Node::Target: Eq + Hash,
+    {
+        let mut map = HashMap::new();
+        for node in self {
+            *map.entry(node.get_elem()).or_default() += 1;
+        }
+        map
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use assert_matches::assert_matches;
-    use more_asserts::assert_lt;
+    /// Return `true` if `node` is in `self`.
+    pub fn contains(&self, node: &Node) -> bool {
+        self.iter().any(|it| ptr::eq(it, node))
+    }
 
-    use concurrency_toolkit::sync::{Arc, RwLock};
-    use concurrency_toolkit::{spawn, join, yield_now};
+    /// Return nodes of `self` along with their distance from the last node.
+    #[cfg(feature = "alloc")]
+    pub fn iter_with_rindex(&self) -> impl Iterator<Item = (usize, &'a Node)> + '_ {
+        let nodes: Vec<&'a Node> = self.iter().collect();
+        let len = nodes.len();
 
-    use once_cell::sync::Lazy;
+        nodes.into_iter().enumerate().map(move |(index, node)| (len - 1 - index, node))
+    }
 
-    type Node<T = usize> = IntrusiveListNodeImpl<T>;
+    /// Fold nodes of `self` from the last node to the first node.
+    pub fn rfold<B>(&self, init: B, f: impl FnMut(B, &Node) -> B) -> B {
+        self.iter().rfold(init, f)
+    }
 
-    fn setup() -> Vec<Node> {
-        (0..100).map(Node::new).collect()
+    /// Return an iterator over elements of `self` that supports `peek`.
+    pub fn iter_peekable_elems(&self) -> PeekableElems<'a, '_, Node, P> {
+        PeekableElems {
+            iter: self.iter().peekable(),
+        }
     }
 
-    #[concurrency_toolkit::test]
-    fn test_splice_empty() {
-        let splice: Splice<'_, Node> = Default::default();
+    /// Return an iterator over elements of `self`, which has the same
+    /// stale read semantics as `IntrusiveList::iter`.
+    ///
+    /// ```
+    /// use concurrent_read_push_list::intrusive_list::*;
+    ///
+    /// let nodes: Vec<_> = (0..3).map(IntrusiveListNodeImpl::new).collect();
+    ///
+    /// let list = IntrusiveList::new();
+    /// for node in &nodes {
+    ///     unsafe { list.push_back(node) };
+    /// }
+    ///
+    /// assert_eq!(vec![&0, &1, &2], list.iter_elems().collect::<Vec<_>>());
+    /// ```
+    pub fn iter_elems(&self) -> impl DoubleEndedIterator<Item = Node::Target> + '_ {
+        self.iter().map(|node| node.get_elem())
+    }
 
-        assert!(splice.is_empty());
+    /// Return the first node of `self`.
+    ///
+    /// If there are concurrent pushes, the node returned might no longer be
+    /// the first node by the time it is used.
+    pub fn front(&self) -> Option<&'a Node> {
+        self.iter().next()
     }
 
-    #[concurrency_toolkit::test]
-    fn test_splice_push_back_splice() {
-        let nodes = setup();
+    /// Return the last node of `self`.
+    ///
+    /// If there are concurrent pushes, the node returned might no longer be
+    /// the last node by the time it is used.
+    pub fn back(&self) -> Option<&'a Node> {
+        self.iter().next_back()
+    }
 
-        let mut splice0: Splice<'_, _> = Default::default();
+    /// Return # num of leading nodes of `self` that `f` returns true.
+    pub fn count_prefix(&self, f: impl Fn(&Node) -> bool) -> usize {
+        self.iter().take_while(|node| f(node)).count()
+    }
 
-        for node in &nodes[0..50] {
-            unsafe { splice0.push_back(node) };
-            assert!(!splice0.is_empty());
-        }
+    /// Return the first node of `self` that `f` returns true, without
+    /// visiting the rest of the list.
+    ///
+    /// The node returned is only guaranteed to be in `self` while the read
+    /// lock is held, since it might be removed, e.g. by `remove_node`,
+    /// once the lock is released.
+    pub fn find(&self, mut f: impl FnMut(&Node) -> bool) -> Option<&'a Node> {
+        self.iter().find(|node| f(node))
+    }
 
-        for (index, node) in splice0.iter().enumerate() {
-            assert_eq!(index, *node.get_elem());
-            assert_lt!(index, 50);
+    /// Return # num of nodes of `self` that `f` returns true.
+    ///
+    /// If there are concurrent pushes, the count is taken over the nodes seen
+    /// while walking the list, which might not include nodes pushed during
+    /// the walk.
+    pub fn count_if(&self, mut f: impl FnMut(&Node) -> bool) -> usize {
+        self.iter().filter(|node| f(node)).count()
+    }
+
+    /// Return `(key, first, last)` for each maximal run of consecutive nodes
+    /// in `self` that have equal keys.
+    #[cfg(feature = "alloc")]
+    pub fn iter_grouped_runs<K: PartialEq>(
+        &self,
+        key: impl Fn(&Node) -> K
+    ) -> impl Iterator<Item = (K, &'a Node, &'a Node)> {
+        let mut runs: Vec<(K, &'a Node, &'a Node)> = Vec::new();
+
+        for node in self {
+            let k = key(node);
+            match runs.last_mut() {
+                Some((run_key, _, last)) if *run_key == k => *last = node,
+                _ => runs.push((k, node, node)),
+            }
         }
 
-        let mut splice1: Splice<'_, _> = Default::default();
+        runs.into_iter()
+    }
 
-        for node in &nodes[50..100] {
-            unsafe { splice1.push_back(node) };
-            assert!(!splice1.is_empty());
+    /// Return `true` if elements of `self` are a rotation of elements of
+    /// `other`.
+    #[cfg(feature = "alloc")]
+    pub fn is_rotation_of(&self, other: &IntrusiveList<'a, Node, P>) -> bool
+    where
+        Node::Target: PartialEq,
+    {
+        let elems: Vec<_> = self.iter_elems().collect();
+        let other: Vec<_> = other.iter_elems().collect();
+
+        if elems.len() != other.len() {
+            return false;
+        }
+        if elems.is_empty() {
+            return true;
         }
 
-        for (index, node) in splice1.iter().enumerate() {
-            assert_eq!(index + 50, *node.get_elem());
-            assert_lt!(index, 50);
+        let doubled: Vec<_> = other.iter().chain(other.iter()).collect();
+        doubled
+            .windows(elems.len())
+            .any(|window| window.iter().zip(&elems).all(|(x, y)| *x == y))
+    }
+
+    // All methods below are removal methods, which takes the write lock:
+
+    /// Returns `true` if `node` is indeed inside `self`, otherwise `false`.
+    ///
+    /// # Safety
+    ///
+    ///  * `node` - it must be in one of the following state:
+    ///     - `node.get_next_ptr().is_null() && node.get_prev_ptr().is_null()`
+    ///     - `node` is added to `self`
+    ///
+    ///    and, __**YOU MUST NOT USE IT IN OTHER LISTS/SPLICES SIMULTANEOUSLY OR
+    ///    ADD IT TO THE SAME LIST SIMULTANEOUSLY
+    ///    but you can REMOVE IT FROM THE SAME LIST SIMULTANEOUSLY**__.
+    pub unsafe fn remove_node(&mut self, node: &'a Node) -> bool {
+        self.splice_impl(node, node).is_some()
+    }
+
+    ///  * `f` - return true to remove the node or false to keep it
+    /// 
+    /// Return (# num of elements left, # num of elements removed)
+    pub fn remove_if(&mut self, mut f: impl FnMut(&'a Node) -> bool) -> (usize, usize) {
+        use Ordering::Relaxed;
+
+        let mut it = self.first_ptr.load(Relaxed);
+
+        let mut prev: *const Node = ptr::null();
+        let mut beg: *const Node = ptr::null();
+
+        let mut cnt = (0, 0);
+
+        while !it.is_null() {
+            let node = unsafe { &* (it as *mut Node as *const Node) };
+            cnt.0 += 1;
+            if f(node) {
+                cnt.1 += 1;
+                if beg.is_null() {
+                    beg = node;
+                }
+            } else if !beg.is_null() {
+                unsafe {
+                    self.splice_impl(&* beg, &* prev).unwrap();
+                    Self::reset_links(&* beg, &* prev);
+                }
+                beg = ptr::null();
+            }
+            prev = node;
+            it = node.get_next_ptr().load(Relaxed);
+        }
+
+        if !beg.is_null() {
+            unsafe {
+                self.splice_impl(&* beg, &* prev).unwrap();
+                Self::reset_links(&* beg, &* prev);
+            }
+        }
+
+        cnt.0 -= cnt.1;
+
+        cnt
+    }
+
+    /// Remove every node whose element equals to `target`.
+    ///
+    /// Return # num of elements removed.
+    pub fn remove_all_eq(&mut self, target: &Node::Target) -> usize
+    where
+        Node::Target: PartialEq,
+    {
+        self.remove_if(|node| node.get_elem() == *target).1
+    }
+
+    /// Same as `remove_if`, except that `f` is given the element of the node.
+    ///
+    /// Return (# num of elements left, # num of elements removed)
+    pub fn remove_if_elem(&mut self, mut f: impl FnMut(Node::Target) -> bool) -> (usize, usize) {
+        self.remove_if(|node| f(node.get_elem()))
+    }
+
+    /// Remove all nodes from `self`.
+    ///
+    /// Next and prev pointers of every node removed are reset to null,
+    /// so that they can be added to a list again.
+    pub fn clear(&mut self) {
+        use Ordering::Relaxed;
+
+        let null = ptr::null_mut();
+
+        let mut it = self.first_ptr.load(Relaxed);
+        while !it.is_null() {
+            let node = unsafe { &* (it as *mut Node as *const Node) };
+            it = node.get_next_ptr().load(Relaxed);
+
+            node.get_next_ptr().store(null, Relaxed);
+            node.get_prev_ptr().store(null, Relaxed);
+        }
+
+        self.first_ptr.store(null, Relaxed);
+        self.last_ptr .store(null, Relaxed);
+
+        #[cfg(feature = "counter")]
+        self.len.store(0, Relaxed);
+    }
+
+    /// Move all list nodes between `first` and `last` (inclusive) from `self`
+    /// and return `Some(())`.
+    ///
+    /// Or return `None` if `first` or `last` does not belong to `self`.
+    ///
+    /// # Safety
+    ///
+    ///  * `first`, `last` - `first` must be to the left of the `last` 
+    ///    (`first` can be the same node as `last`) and
+    ///    __**YOU MUST NOT USE IT IN OTHER LISTS/SPLICES SIMULTANEOUSLY OR
+    ///    ADD IT TO THE SAME LIST SIMULTANEOUSLY
+    ///    but you can REMOVE IT FROM THE SAME LIST SIMULTANEOUSLY**__.
+    ///
+    /// Must be called after obtained a write lock of `self.rwlock`.
+    #[must_use]
+    unsafe fn splice_impl(&mut self, first: &'a Node, last: &'a Node) -> Option<()> {
+        use Ordering::Relaxed;
+
+        let (first_node, last_node) = (first, last);
+
+        let prev_node = first.get_prev_ptr().load(Relaxed);
+        let next_node = last .get_next_ptr().load(Relaxed);
+
+        #[cfg(feature = "counter")]
+        let cnt = {
+            let mut cnt = 1;
+            let mut it: &Node = first;
+            while !ptr::eq(it, last) {
+                it = &*(it.get_next_ptr().load(Relaxed) as *mut Node as *const Node);
+                cnt += 1;
+            }
+            cnt
+        };
+
+        let last_ptr = if next_node.is_null() {
+            &self.last_ptr
+        } else {
+            let next_node = next_node as *mut Node;
+            (*next_node).get_prev_ptr()
+        };
+        let last = last as *const _ as *mut ();
+        match last_ptr.compare_exchange(last, prev_node, Relaxed, Relaxed) {
+            Ok(_) => (),
+            Err(_) => return None,
+        }
+
+        let first_ptr = if prev_node.is_null() {
+            &self.first_ptr
+        } else {
+            let prev_node = prev_node as *mut Node;
+            (*prev_node).get_next_ptr()
+        };
+        let first = first as *const _ as *mut ();
+        if ptr::eq(first, last) {
+            assert_store_ptr_relaxed(first_ptr, first, next_node);
+        } else {
+            match first_ptr.compare_exchange(first, next_node, Relaxed, Relaxed) {
+                Ok(_) => (),
+                Err(_) => {
+                    // Revert the change of last_ptr
+                    assert_store_ptr_relaxed(last_ptr, prev_node, last);
+                    return None
+                },
+            }
+        }
+
+        // Detach the nodes removed from the rest of `self`
+        first_node.get_prev_ptr().store(ptr::null_mut(), Relaxed);
+        last_node .get_next_ptr().store(ptr::null_mut(), Relaxed);
+
+        #[cfg(feature = "counter")]
+        self.len.fetch_sub(cnt, Relaxed);
+
+        Some(())
+    }
+
+    /// Reset next and prev pointers of nodes between `first` and `last`
+    /// (inclusive) to null.
+    ///
+    /// # Safety
+    ///
+    ///  * `first` and `last` - must be linked, `first` on the left of `last`,
+    ///    and not be in any list.
+    unsafe fn reset_links(first: &'a Node, last: &'a Node) {
+        use Ordering::Relaxed;
+
+        let null = ptr::null_mut();
+
+        let mut it = first;
+        loop {
+            let next = it.get_next_ptr().load(Relaxed);
+
+            it.get_next_ptr().store(null, Relaxed);
+            it.get_prev_ptr().store(null, Relaxed);
+
+            if ptr::eq(it, last) {
+                break;
+            }
+            it = &*(next as *mut Node as *const Node);
+        }
+    }
+
+    /// Link `node` right before `next`.
+    ///
+    /// # Safety
+    ///
+    ///  * `next` - must be in `self`.
+    ///  * `node` - must not be in any list/splice.
+    unsafe fn link_before(&mut self, next: &'a Node, node: &'a Node) {
+        use Ordering::Relaxed;
+
+        let prev = next.get_prev_ptr().load(Relaxed);
+
+        node.get_prev_ptr().store(prev, Relaxed);
+        node.get_next_ptr().store(next as *const _ as *mut (), Relaxed);
+
+        let node = node as *const _ as *mut ();
+        next.get_prev_ptr().store(node, Relaxed);
+        if prev.is_null() {
+            self.first_ptr.store(node, Relaxed);
+        } else {
+            (*(prev as *mut Node)).get_next_ptr().store(node, Relaxed);
+        }
+
+        #[cfg(feature = "counter")]
+        self.len.fetch_add(1, Relaxed);
+    }
+
+    /// Link `node` right after `prev`.
+    ///
+    /// # Safety
+    ///
+    ///  * `prev` - must be in `self`.
+    ///  * `node` - must not be in any list/splice.
+    unsafe fn link_after(&mut self, prev: &'a Node, node: &'a Node) {
+        use Ordering::Relaxed;
+
+        let next = prev.get_next_ptr().load(Relaxed);
+
+        node.get_next_ptr().store(next, Relaxed);
+        node.get_prev_ptr().store(prev as *const _ as *mut (), Relaxed);
+
+        let node = node as *const _ as *mut ();
+        prev.get_next_ptr().store(node, Relaxed);
+        if next.is_null() {
+            self.last_ptr.store(node, Relaxed);
+        } else {
+            (*(next as *mut Node)).get_prev_ptr().store(node, Relaxed);
+        }
+
+        #[cfg(feature = "counter")]
+        self.len.fetch_add(1, Relaxed);
+    }
+
+    /// Move all list nodes between `first` and `last` (inclusive) from `self`
+    /// and return them as `Some(Splice)`.
+    ///
+    /// Or return `None` if `first` or `last` does not belong to `self`.
+    ///
+    /// # Safety
+    ///
+    ///  * `first`, `last` - `first` must be to the left of the `last` and
+    ///    __**YOU MUST NOT USE IT IN OTHER LISTS/SPLICES SIMULTANEOUSLY OR
+    ///    ADD IT TO THE SAME LIST SIMULTANEOUSLY
+    ///    but you can REMOVE IT FROM THE SAME LIST SIMULTANEOUSLY**__.
+    #[must_use]
+    pub unsafe fn splice(
+        &mut self,
+        first: &'a Node,
+        last: &'a Node
+    ) -> Option<Splice<'a, Node>> {
+        self.splice_impl(first, last).map(|_| {Splice::new_unchecked(first, last)})
+    }
+
+    /// Remove all nodes from `self`, then push `nodes` to the back of `self`.
+    ///
+    /// Return nodes removed from `self`.
+    ///
+    /// # Safety
+    ///
+    ///  * `nodes` -  __**YOU MUST NOT USE THEM IN OTHER LISTS/SPLICES SIMULTANEOUSLY OR
+    ///    ADD THEM TO THE SAME LIST SIMULTANEOUSLY**__.
+    #[cfg(feature = "alloc")]
+    pub unsafe fn replace_all(&mut self, nodes: &[&'a Node]) -> Vec<&'a Node> {
+        let old_nodes = self.iter().collect();
+
+        self.clear();
+        for node in nodes {
+            self.push_back(node);
+        }
+
+        old_nodes
+    }
+
+    /// Move every node that `f` returns true to the front of `self`,
+    /// while preserving their relative order.
+    #[cfg(feature = "alloc")]
+    pub fn move_matching_to_front(&mut self, f: impl Fn(&Node) -> bool) {
+        let matched: Vec<&'a Node> = self.iter().filter(|node| f(node)).collect();
+
+        let mut splice = Splice::new_empty();
+        for node in matched {
+            unsafe {
+                self.splice_impl(node, node).unwrap();
+                splice.push_back(node);
+            }
+        }
+
+        self.push_front_splice(splice);
+    }
+
+    /// Rotate `self` to the left by `amount(len) % len`, where `len` is
+    /// the number of nodes in `self`.
+    pub fn rotate_by(&mut self, amount: impl FnOnce(usize) -> usize) {
+        #[cfg(feature = "counter")]
+        let len = self.len();
+        #[cfg(not(feature = "counter"))]
+        let len = self.iter().count();
+
+        let amount = amount(len);
+
+        if len == 0 {
+            return;
+        }
+        let amount = amount % len;
+        if amount == 0 {
+            return;
+        }
+
+        let first = self.iter().next().unwrap();
+        let last  = self.iter().nth(amount - 1).unwrap();
+
+        let splice = unsafe { self.splice(first, last) }.unwrap();
+        self.push_back_splice(splice);
+    }
+
+    /// Return an iterator that removes and returns the first node of `self`
+    /// on each call to `next`.
+    ///
+    /// Nodes returned have their next and prev pointer reset to null.
+    pub fn consuming_iter(&mut self) -> impl Iterator<Item = &'a Node> + '_ {
+        iter::from_fn(move || self.pop_front())
+    }
+
+    /// Remove all nodes from `self` and return an iterator over them.
+    ///
+    /// Nodes returned have their next and prev pointer reset to null,
+    /// so that they can be added to a list again.
+    ///
+    /// Nodes not yet returned when `Drain` is dropped are reset as well.
+    pub fn drain(&mut self) -> Drain<'a, Node> {
+        use Ordering::Relaxed;
+
+        let null = ptr::null_mut();
+
+        let drain = Drain {
+            first_ptr: self.first_ptr.swap(null, Relaxed),
+            last_ptr:  self.last_ptr .swap(null, Relaxed),
+            phantom: PhantomData,
+        };
+
+        #[cfg(feature = "counter")]
+        self.len.store(0, Relaxed);
+
+        drain
+    }
+
+    /// Remove and return the first node of `self`, with its next and prev
+    /// pointer reset to null.
+    pub fn pop_front(&mut self) -> Option<&'a Node> {
+        let node = self.iter().next()?;
+        Some(unsafe { self.pop_node(node) })
+    }
+
+    /// Remove and return the last node of `self`, with its next and prev
+    /// pointer reset to null.
+    pub fn pop_back(&mut self) -> Option<&'a Node> {
+        let node = self.iter().next_back()?;
+        Some(unsafe { self.pop_node(node) })
+    }
+
+    /// # Safety
+    ///
+    ///  * `node` - must be in `self`.
+    unsafe fn pop_node(&mut self, node: &'a Node) -> &'a Node {
+        use Ordering::Relaxed;
+
+        self.splice_impl(node, node).unwrap();
+
+        let null = ptr::null_mut();
+        node.get_next_ptr().store(null, Relaxed);
+        node.get_prev_ptr().store(null, Relaxed);
+
+        node
+    }
+
+    /// Swap `node` with the node before it.
+    ///
+    /// Return `false` if `node` is the first node.
+    ///
+    /// # Safety
+    ///
+    ///  * `node` - must be in `self`.
+    pub unsafe fn promote(&mut self, node: &'a Node) -> bool {
+        let prev = node.get_prev_ptr().load(Ordering::Relaxed);
+        if prev.is_null() {
+            return false;
+        }
+        let prev = &*(prev as *mut Node as *const Node);
+
+        self.splice_impl(node, node).unwrap();
+        self.link_before(prev, node);
+
+        true
+    }
+
+    /// Swap `node` with the node after it.
+    ///
+    /// Return `false` if `node` is the last node.
+    ///
+    /// # Safety
+    ///
+    ///  * `node` - must be in `self`.
+    pub unsafe fn demote(&mut self, node: &'a Node) -> bool {
+        let next = node.get_next_ptr().load(Ordering::Relaxed);
+        if next.is_null() {
+            return false;
+        }
+        let next = &*(next as *mut Node as *const Node);
+
+        self.splice_impl(node, node).unwrap();
+        self.link_after(next, node);
+
+        true
+    }
+
+    /// Exchange the positions of `a` and `b` in `self`.
+    ///
+    /// Return false and leave `self` unchanged if either of them is not
+    /// in `self`.
+    pub fn swap_nodes(&mut self, a: &'a Node, b: &'a Node) -> bool {
+        use Ordering::Relaxed;
+
+        if !self.contains(a) || !self.contains(b) {
+            return false;
+        }
+        if ptr::eq(a, b) {
+            return true;
+        }
+
+        let is_next = |node: &'a Node, next: &'a Node| {
+            ptr::eq(node.get_next_ptr().load(Relaxed), next as *const _ as *mut ())
+        };
+
+        unsafe {
+            if is_next(a, b) {
+                self.demote(a);
+            } else if is_next(b, a) {
+                self.demote(b);
+            } else {
+                let prev = (a.get_prev_ptr().load(Relaxed) as *const Node).as_ref();
+                let next = (a.get_next_ptr().load(Relaxed) as *const Node).as_ref();
+
+                self.splice_impl(a, a).unwrap();
+                self.link_before(b, a);
+                self.splice_impl(b, b).unwrap();
+
+                // Since `a` and `b` are not adjacent, neither of `prev` and
+                // `next` can be `b` and at least one of them exists.
+                match (prev, next) {
+                    (Some(prev), _) => self.link_after(prev, b),
+                    (None, Some(next)) => self.link_before(next, b),
+                    (None, None) => unreachable!(),
+                }
+            }
+        }
+
+        true
+    }
+
+    /// Clear `self` if `pred` returns true for the number of nodes in `self`.
+    ///
+    /// Return `true` if `self` is cleared.
+    pub fn clear_if(&mut self, pred: impl FnOnce(usize) -> bool) -> bool {
+        #[cfg(feature = "counter")]
+        let len = self.len();
+        #[cfg(not(feature = "counter"))]
+        let len = self.iter().count();
+
+        if pred(len) {
+            self.clear();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Remove and return the first node of `self`, or push the node returned
+    /// by `make` to `self` and return it if `self` is empty.
+    ///
+    /// # Safety
+    ///
+    ///  * `make` - node returned __**MUST NOT BE USED IN OTHER LISTS/SPLICES SIMULTANEOUSLY OR
+    ///    ADDED TO THE SAME LIST SIMULTANEOUSLY**__.
+    pub unsafe fn pop_front_or_insert(&mut self, make: impl FnOnce() -> &'a Node) -> &'a Node {
+        let popped = self.pop_front();
+        popped.unwrap_or_else(|| {
+            let node = make();
+            self.push_back(node);
+            node
+        })
+    }
+
+    /// Remove and return the last node of `self`, or push the node returned
+    /// by `make` to `self` and return it if `self` is empty.
+    ///
+    /// # Safety
+    ///
+    ///  * `make` - node returned __**MUST NOT BE USED IN OTHER LISTS/SPLICES SIMULTANEOUSLY OR
+    ///    ADDED TO THE SAME LIST SIMULTANEOUSLY**__.
+    pub unsafe fn pop_back_or_insert(&mut self, make: impl FnOnce() -> &'a Node) -> &'a Node {
+        let popped = self.pop_back();
+        popped.unwrap_or_else(|| {
+            let node = make();
+            self.push_back(node);
+            node
+        })
+    }
+
+    /// Reverse the order of nodes in `self`.
+    pub fn reverse(&mut self) {
+        use Ordering::Relaxed;
+
+        let first = self.first_ptr.load(Relaxed);
+        let last  = self.last_ptr .load(Relaxed);
+
+        // Empty list or list with only one node
+        if first == last {
+            return;
+        }
+
+        let first_node = unsafe { &* (first as *mut Node as *const Node) };
+        let last_node  = unsafe { &* (last  as *mut Node as *const Node) };
+
+        if first_node.get_next_ptr().load(Relaxed) == last {
+            // Fast path for list with exactly two nodes
+            first_node.get_prev_ptr().store(last, Relaxed);
+            first_node.get_next_ptr().store(ptr::null_mut(), Relaxed);
+
+            last_node.get_next_ptr().store(first, Relaxed);
+            last_node.get_prev_ptr().store(ptr::null_mut(), Relaxed);
+        } else {
+            let mut it = first;
+            while !it.is_null() {
+                let node = unsafe { &* (it as *mut Node as *const Node) };
+
+                let next = node.get_next_ptr().load(Relaxed);
+                node.get_next_ptr().store(node.get_prev_ptr().load(Relaxed), Relaxed);
+                node.get_prev_ptr().store(next, Relaxed);
+
+                it = next;
+            }
+        }
+
+        self.first_ptr.store(last,  Relaxed);
+        self.last_ptr .store(first, Relaxed);
+    }
+
+    /// Push `node` to the back of `self`, then remove and return the first
+    /// node if `self` contains more than `cap` nodes.
+    ///
+    /// # Safety
+    ///
+    ///  * `node` -  __**YOU MUST NOT USE IT IN OTHER LISTS/SPLICES SIMULTANEOUSLY OR
+    ///    ADD IT TO THE SAME LIST SIMULTANEOUSLY**__.
+    pub unsafe fn push_back_bounded_evict(
+        &mut self,
+        node: &'a Node,
+        cap: usize
+    ) -> Option<&'a Node> {
+        self.push_back(node);
+
+        #[cfg(feature = "counter")]
+        let over_cap = self.len() > cap;
+        #[cfg(not(feature = "counter"))]
+        let over_cap = self.iter().nth(cap).is_some();
+
+        if over_cap {
+            self.pop_front()
+        } else {
+            None
+        }
+    }
+
+    /// Find the first node that `start` returns true and the first node after
+    /// it that `end` returns true, then move them and all nodes between them
+    /// out of `self` into a new list.
+    ///
+    /// Return `None` if no such node is found.
+    pub fn extract_between(
+        &mut self,
+        start: impl Fn(&Node) -> bool,
+        end: impl Fn(&Node) -> bool
+    ) -> Option<IntrusiveList<'a, Node, P>> {
+        let mut iter = self.iter();
+
+        let first = iter.find(|node| start(node))?;
+        let last  = iter.find(|node| end(node))?;
+
+        let splice = unsafe { self.splice(first, last) }.unwrap();
+        Some(Self::from_splice(splice))
+    }
+
+    /// Make `new_next` the node after `node`, or make `node` the last node if
+    /// `new_next` is `None`.
+    ///
+    /// The prev pointer of `new_next` is set to `node`.
+    ///
+    /// The first and last node of `self` are then updated to the ends of the
+    /// chain containing `node`, nodes that are no longer reachable from `node`
+    /// are no longer in `self` and have their links reset to null.
+    ///
+    /// # Safety
+    ///
+    ///  * `node` - must be in `self`.
+    ///  * `new_next` - must not create a cycle and
+    ///    __**YOU MUST NOT USE IT IN OTHER LISTS/SPLICES SIMULTANEOUSLY**__.
+    pub unsafe fn set_next(&mut self, node: &'a Node, new_next: Option<&'a Node>) {
+        use Ordering::Relaxed;
+
+        let null = ptr::null_mut();
+
+        let new_next_ptr = new_next.map_or(null, |new_next| new_next as *const _ as *mut ());
+
+        let old_next = node.get_next_ptr().load(Relaxed);
+
+        // Nodes from `old_next` up to `new_next` or the end of `self` are
+        // dropped from `self`.
+        let mut dropped = None;
+        if !old_next.is_null() && old_next != new_next_ptr {
+            let first_dropped = &*(old_next as *mut Node as *const Node);
+            let mut last_dropped = first_dropped;
+            loop {
+                let next = last_dropped.get_next_ptr().load(Relaxed);
+                if next.is_null() || next == new_next_ptr {
+                    break;
+                }
+                last_dropped = &*(next as *mut Node as *const Node);
+            }
+            dropped = Some((first_dropped, last_dropped));
+
+            first_dropped.get_prev_ptr().store(null, Relaxed);
+        }
+
+        match new_next {
+            Some(new_next) => {
+                let prev = new_next.get_prev_ptr().load(Relaxed);
+                if !prev.is_null() {
+                    (*(prev as *mut Node)).get_next_ptr().store(null, Relaxed);
+                }
+
+                new_next.get_prev_ptr().store(node as *const _ as *mut (), Relaxed);
+                node.get_next_ptr().store(new_next as *const _ as *mut (), Relaxed);
+            },
+            None => node.get_next_ptr().store(null, Relaxed),
+        }
+
+        if let Some((first_dropped, last_dropped)) = dropped {
+            Self::reset_links(first_dropped, last_dropped);
+        }
+
+        let mut first = node;
+        while let Some(prev) = (first.get_prev_ptr().load(Relaxed) as *const Node).as_ref() {
+            first = prev;
+        }
+        let mut last = node;
+        while let Some(next) = (last.get_next_ptr().load(Relaxed) as *const Node).as_ref() {
+            last = next;
+        }
+
+        self.first_ptr.store(first as *const _ as *mut (), Relaxed);
+        self.last_ptr .store(last  as *const _ as *mut (), Relaxed);
+
+        #[cfg(feature = "counter")]
+        self.len.store(self.iter().count(), Relaxed);
+    }
+
+    /// Move nodes that `cmp` returns `Less` into the first list returned and
+    /// nodes that `cmp` returns `Greater` into the second list returned,
+    /// while nodes that `cmp` returns `Equal` are kept in `self`.
+    ///
+    /// The relative order of nodes is preserved in all three lists.
+    #[cfg(feature = "alloc")]
+    pub fn partition3(
+        &mut self,
+        cmp: impl Fn(&Node) -> cmp::Ordering
+    ) -> (IntrusiveList<'a, Node, P>, IntrusiveList<'a, Node, P>) {
+        let mut less    = Splice::new_empty();
+        let mut greater = Splice::new_empty();
+
+        let nodes: Vec<&'a Node> = self.iter().collect();
+        for node in nodes {
+            let splice = match cmp(node) {
+                cmp::Ordering::Less    => &mut less,
+                cmp::Ordering::Greater => &mut greater,
+                cmp::Ordering::Equal   => continue,
+            };
+            unsafe {
+                self.splice_impl(node, node).unwrap();
+                splice.push_back(node);
+            }
+        }
+
+        (Self::from_splice(less), Self::from_splice(greater))
+    }
+
+    /// Move the first node that is `less` than the node before it backward
+    /// until it is no longer `less` than the node before it.
+    ///
+    /// Return `false` if `self` is already sorted.
+    ///
+    /// Calling this repeatedly until it returns `false` stable sorts `self`.
+    pub fn insertion_sort_step(&mut self, less: impl Fn(&Node, &Node) -> bool) -> bool {
+        use Ordering::Relaxed;
+
+        let mut iter = self.iter();
+        let mut prev = match iter.next() {
+            Some(node) => node,
+            None => return false,
+        };
+
+        let node = match iter.find(|node| {
+            let out_of_order = less(node, prev);
+            prev = node;
+            out_of_order
+        }) {
+            Some(node) => node,
+            None => return false,
+        };
+
+        let mut next = unsafe { &* (node.get_prev_ptr().load(Relaxed) as *const Node) };
+        while let Some(prev) = unsafe {
+            (next.get_prev_ptr().load(Relaxed) as *const Node).as_ref()
+        } {
+            if !less(node, prev) {
+                break;
+            }
+            next = prev;
+        }
+
+        unsafe {
+            self.splice_impl(node, node).unwrap();
+            self.link_before(next, node);
+        }
+
+        true
+    }
+
+    /// Push `node` to the back of `self`, then remove and return the first
+    /// node if `evict` returns true for it.
+    ///
+    /// # Safety
+    ///
+    ///  * `node` -  __**YOU MUST NOT USE IT IN OTHER LISTS/SPLICES SIMULTANEOUSLY OR
+    ///    ADD IT TO THE SAME LIST SIMULTANEOUSLY**__.
+    pub unsafe fn push_back_evict_if(
+        &mut self,
+        node: &'a Node,
+        evict: impl Fn(&Node) -> bool
+    ) -> Option<&'a Node> {
+        self.push_back(node);
+
+        if evict(self.iter().next().unwrap()) {
+            self.pop_front()
+        } else {
+            None
+        }
+    }
+
+    /// Move the first `n` nodes of `self` (or all nodes if `self` contains
+    /// less than `n` nodes) to the back of `dest`.
+    ///
+    /// `dest` is only pushed to, which other threads can do concurrently
+    /// while reading it, so the read lock of `dest` suffices.
+    ///
+    /// Does nothing if `n` is 0 or `self` is empty.
+    pub fn transfer_front_n(&mut self, n: usize, dest: &IntrusiveList<'a, Node, P>) {
+        let mut iter = self.iter();
+
+        let first = match iter.next() {
+            Some(node) => node,
+            None => return,
+        };
+        if n == 0 {
+            return;
+        }
+        let last = iter.take(n - 1).last().unwrap_or(first);
+
+        let splice = unsafe { self.splice(first, last) }.unwrap();
+        dest.push_back_splice(splice);
+    }
+
+    /// Replace the first node of `self` with `new` if it is `expected`.
+    ///
+    /// On success, `expected` has its next and prev pointer reset to null.
+    ///
+    /// Otherwise, return the actual first node of `self`, or `None` if
+    /// `self` is empty.
+    ///
+    /// # Safety
+    ///
+    ///  * `new` -  __**YOU MUST NOT USE IT IN OTHER LISTS/SPLICES SIMULTANEOUSLY OR
+    ///    ADD IT TO THE SAME LIST SIMULTANEOUSLY**__.
+    pub unsafe fn compare_exchange_front(
+        &mut self,
+        expected: &'a Node,
+        new: &'a Node
+    ) -> Result<(), Option<&'a Node>> {
+        use Ordering::Relaxed;
+
+        match self.iter().next() {
+            Some(first) if ptr::eq(first, expected) => (),
+            first => return Err(first),
+        }
+
+        self.splice_impl(expected, expected).unwrap();
+
+        let null = ptr::null_mut();
+        expected.get_next_ptr().store(null, Relaxed);
+        expected.get_prev_ptr().store(null, Relaxed);
+
+        self.push_front(new);
+
+        Ok(())
+    }
+
+    /// Move every `step`-th node, starting from the first node, out of `self`
+    /// into a new list, while preserving their relative order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `step` is 0.
+    #[cfg(feature = "alloc")]
+    pub fn extract_every_nth(&mut self, step: usize) -> IntrusiveList<'a, Node, P> {
+        assert_ne!(step, 0, "step must not be 0");
+
+        let mut extracted = Splice::new_empty();
+
+        let nodes: Vec<&'a Node> = self.iter().step_by(step).collect();
+        for node in nodes {
+            unsafe {
+                self.splice_impl(node, node).unwrap();
+                extracted.push_back(node);
+            }
+        }
+
+        Self::from_splice(extracted)
+    }
+
+    /// Remove every node that `f` returns true.
+    ///
+    /// Return # num of elements removed.
+    pub fn clear_matching(&mut self, f: impl Fn(&Node) -> bool) -> usize {
+        self.remove_if(f).1
+    }
+
+    /// Move the first node of `self` that satisfies `f` to the back of `dest`.
+    ///
+    /// Return true if a node is moved.
+    pub fn move_first_matching_to(
+        &mut self,
+        f: impl Fn(&Node) -> bool,
+        dest: &IntrusiveList<'a, Node, P>
+    ) -> bool {
+        let node = match self.iter().find(|node| f(node)) {
+            Some(node) => node,
+            None => return false,
+        };
+
+        let splice = unsafe { self.splice(node, node) }.unwrap();
+        dest.push_back_splice(splice);
+
+        true
+    }
+
+    /// Replace content of `self` with `nodes`.
+    ///
+    /// Return nodes previously in `self`, in order.
+    ///
+    /// # Safety
+    ///
+    ///  * `nodes` -  __**YOU MUST NOT USE THEM IN OTHER LISTS/SPLICES SIMULTANEOUSLY
+    ///    OR ADD THEM TO `self` MORE THAN ONCE**__.
+    #[cfg(feature = "alloc")]
+    pub unsafe fn reset_from<I>(&mut self, nodes: I) -> Vec<&'a Node>
+    where
+        I: IntoIterator<Item = &'a Node>,
+    {
+        let old: Vec<_> = self.consuming_iter().collect();
+
+        for node in nodes {
+            self.push_back(node);
+        }
+
+        old
+    }
+
+    /// Pop nodes from the front of `self` as long as `f` returns true for
+    /// the first node.
+    ///
+    /// Return nodes popped in order, with their next and prev pointer
+    /// reset to null.
+    #[cfg(feature = "alloc")]
+    pub fn pop_front_while(&mut self, f: impl Fn(&Node) -> bool) -> Vec<&'a Node> {
+        let mut popped = Vec::new();
+
+        while let Some(node) = self.iter().next() {
+            if !f(node) {
+                break;
+            }
+            popped.push(unsafe { self.pop_node(node) });
+        }
+
+        popped
+    }
+
+    /// Swap the first and the last node of `self`.
+    ///
+    /// Return false if `self` contains less than 2 nodes.
+    pub fn swap_ends(&mut self) -> bool {
+        let mut iter = self.iter();
+        let (first, last) = match (iter.next(), iter.next_back()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return false,
+        };
+
+        unsafe {
+            self.pop_node(first);
+            self.pop_node(last);
+
+            self.push_front(last);
+            self.push_back(first);
+        }
+
+        true
+    }
+
+    /// Move all nodes of `other` to the back of `self` in O(1), leaving
+    /// `other` empty.
+    ///
+    /// Only the write lock of `other` and the read lock of `self` are taken,
+    /// so appending two lists to each other from two threads cannot deadlock
+    /// as taking both write locks in different orders would.
+    pub fn append(&self, other: &mut IntrusiveList<'a, Node, P>) {
+        use Ordering::Relaxed;
+
+        let null = ptr::null_mut();
+
+        let splice = Splice {
+            first_ptr: other.first_ptr.swap(null, Relaxed),
+            last_ptr:  other.last_ptr .swap(null, Relaxed),
+            phantom: PhantomData,
+        };
+
+        #[cfg(feature = "counter")]
+        let cnt = other.len.swap(0, Relaxed);
+
+        self.link_back_splice(splice);
+
+        #[cfg(feature = "counter")]
+        self.len.fetch_add(cnt, P::W_ORD);
+    }
+
+    /// Move `node` and all nodes after it out of `self` into a new list.
+    ///
+    /// Return an empty list if `node` is not in `self`.
+    pub fn split_off(&mut self, node: &'a Node) -> IntrusiveList<'a, Node, P> {
+        if !self.contains(node) {
+            return Self::with_policy();
+        }
+
+        let last = self.iter().next_back().unwrap();
+        let splice = unsafe { self.splice(node, last) }.unwrap();
+
+        Self::from_splice(splice)
+    }
+
+    /// Split `self` at the first node that `f` returns true, return the
+    /// matched node and all nodes after it as a new list.
+    ///
+    /// Return `None` if no node matches.
+    pub fn split_at_first(&mut self, f: impl Fn(&Node) -> bool) -> Option<IntrusiveList<'a, Node, P>> {
+        let node = self.iter().find(|node| f(node))?;
+        Some(self.split_off(node))
+    }
+
+    /// Link the last node of `self` to the first node, turning `self` into
+    /// a ring that can be walked via `get_next_ptr`/`get_prev_ptr` endlessly.
+    ///
+    /// `iter` still stops at the last node.
+    ///
+    /// # Safety
+    ///
+    /// Until `break_cyclic` is called, `self` must only be read via `iter`:
+    /// pushing would spin forever while `remove_if`, `clear` and other methods
+    /// walking the links would never terminate.
+    pub unsafe fn make_cyclic(&mut self) {
+        use Ordering::Relaxed;
+
+        let first = self.first_ptr.load(Relaxed);
+        let last  = self.last_ptr .load(Relaxed);
+
+        if first.is_null() {
+            return;
+        }
+
+        (*(last  as *mut Node)).get_next_ptr().store(first, Relaxed);
+        (*(first as *mut Node)).get_prev_ptr().store(last,  Relaxed);
+    }
+
+    /// Undo `make_cyclic`.
+    ///
+    /// Does nothing if `self` is not cyclic.
+    pub fn break_cyclic(&mut self) {
+        use Ordering::Relaxed;
+
+        let null = ptr::null_mut();
+
+        if let (Some(first), Some(last)) = (self.front(), self.back()) {
+            last .get_next_ptr().store(null, Relaxed);
+            first.get_prev_ptr().store(null, Relaxed);
+        }
+    }
+
+    /// Remove every node in `nodes` from `self`.
+    ///
+    /// Nodes not in `self` are ignored and nodes appearing multiple times are
+    /// only removed once.
+    ///
+    /// Return # num of nodes removed.
+    #[cfg(feature = "alloc")]
+    pub fn remove_all(&mut self, nodes: &[&'a Node]) -> usize {
+        let nodes: BTreeSet<*const Node> = nodes.iter()
+            .map(|node| *node as *const Node)
+            .collect();
+
+        self.remove_if(|node| nodes.contains(&(node as *const Node))).1
+    }
+
+    /// Merge nodes in `splice` into `self`, assuming both are sorted by `less`.
+    ///
+    /// Nodes in `splice` are placed after nodes in `self` that are equal.
+    #[cfg(feature = "alloc")]
+    pub fn merge_splice_sorted(
+        &mut self,
+        splice: Splice<'a, Node>,
+        less: impl Fn(&Node, &Node) -> bool
+    ) {
+        use Ordering::Relaxed;
+
+        let nodes: Vec<&'a Node> = splice.iter().collect();
+
+        let mut cursor = self.front();
+        for node in nodes.iter().copied() {
+            while let Some(curr) = cursor {
+                if less(node, curr) {
+                    break;
+                }
+                cursor = unsafe {
+                    (curr.get_next_ptr().load(Relaxed) as *const Node).as_ref()
+                };
+            }
+
+            match cursor {
+                Some(next) => unsafe { self.link_before(next, node) },
+                None => {
+                    // The rest of `splice` is still linked, so it can be
+                    // pushed as a whole.
+                    let last = nodes[nodes.len() - 1];
+                    self.push_back_splice(unsafe { Splice::new_unchecked(node, last) });
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Pop nodes from the back of `self` as long as `f` returns true for
+    /// the last node, and push their elements to `sink` in the order popped.
+    #[cfg(feature = "alloc")]
+    pub fn pop_back_while_into(
+        &mut self,
+        f: impl Fn(&Node) -> bool,
+        sink: &mut Vec<Node::Target>
+    ) {
+        while let Some(node) = self.back() {
+            if !f(node) {
+                break;
+            }
+            sink.push(unsafe { self.pop_node(node) }.get_elem());
+        }
+    }
+
+    /// Push `node` to the back of `self` unless `eq` returns true for it and
+    /// the last node.
+    ///
+    /// Return true if `node` is pushed.
+    ///
+    /// # Safety
+    ///
+    ///  * `node` -  __**YOU MUST NOT USE IT IN OTHER LISTS/SPLICES SIMULTANEOUSLY OR
+    ///    ADD IT TO THE SAME LIST SIMULTANEOUSLY**__.
+    pub unsafe fn push_back_dedup(
+        &mut self,
+        node: &'a Node,
+        eq: impl Fn(&Node, &Node) -> bool
+    ) -> bool {
+        if let Some(last) = self.back() {
+            if eq(node, last) {
+                return false;
+            }
+        }
+
+        self.push_back(node);
+        true
+    }
+}
+impl<'a, Node: IntrusiveListNodeMut<'a>, P: OrderingPolicy> IntrusiveList<'a, Node, P> {
+    /// Apply `f` to the element of each node in `self`.
+    ///
+    /// # Safety
+    ///
+    /// There must be no other reference to the elements of nodes in `self`.
+    pub unsafe fn update_each(&mut self, mut f: impl FnMut(&mut Node::Elem)) {
+        for node in self.iter() {
+            f(node.get_elem_mut());
+        }
+    }
+}
+
+impl<'a, Node: KeyedNode<'a>, P: OrderingPolicy> IntrusiveList<'a, Node, P> {
+    /// Collect `(key, elem)` of every node in `self` into a `BTreeMap`.
+    ///
+    /// If multiple nodes have the same key, the last one is kept.
+    #[cfg(feature = "alloc")]
+    pub fn to_btree_map(&self) -> BTreeMap<Node::Key, Node::Target>
+    where
+        Node::Key: Ord + Clone,
+        Node::Target: Clone,
+    {
+        self.iter()
+            .map(|node| (node.get_key().clone(), node.get_elem()))
+            .collect()
+    }
+}
+
+impl<'a, Node: TaggedNode<'a>, P: OrderingPolicy> IntrusiveList<'a, Node, P> {
+    /// Return `true` if the list tag of every node in `self` is the address
+    /// of `self`.
+    pub fn audit_ownership(&self) -> bool {
+        let tag = self as *const Self as *mut ();
+        self.iter().all(|node| node.get_list_tag().load(P::R_ORD) == tag)
+    }
+}
+
+/// Statistics of elements in an `IntrusiveList`, returned by
+/// `IntrusiveList::stats`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ListStats<T> {
+    pub count: usize,
+    pub min: Option<T>,
+    pub max: Option<T>,
+}
+
+/// Result of walking an `IntrusiveList` in both direction, returned by
+/// `IntrusiveList::link_report`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct LinkReport {
+    /// # num of nodes reached from the first node via next pointers
+    pub forward_len: usize,
+    /// # num of nodes reached from the last node via prev pointers
+    pub backward_len: usize,
+    /// `true` if both walk reach the same number of nodes, and the first
+    /// node has null prev pointer and the last node has null next pointer.
+    pub consistent: bool,
+}
+
+/// `Splice` can be used to
+///  - move list nodes between `IntrusiveList` efficiently;
+///  - insert/remove list nodes from one `IntrusiveList` efficiently;
+///  - iterate over list and remove nodes without starving the rest of readers/removers
+///    waiting on `IntrusiveList`.
+pub struct Splice<'a, Node: IntrusiveListNode<'a>> {
+    first_ptr: * mut (),
+    last_ptr: *mut (),
+    phantom: PhantomData<&'a Node>,
+}
+unsafe impl<'a, Node: IntrusiveListNode<'a> + Debug> Send for Splice<'a, Node> {}
+impl<'a, Node: IntrusiveListNode<'a>> Debug for Splice<'a, Node>
+where
+    Node::Target: Debug,
+{
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        debug_elems(fmt, self.iter())
+    }
+}
+impl<'a, Node: IntrusiveListNode<'a>> Default for Splice<'a, Node> {
+    fn default() -> Self {
+        Self::new_empty()
+    }
+}
+impl<'a, Node: IntrusiveListNode<'a>> Splice<'a, Node> {
+    /// # Safety
+    ///
+    /// Assumes `first` and `last` is already linked, `first` must be to the
+    /// left of the `last` (`first` and `last` can be the same node)
+    /// and and the link must not be modified after `Splice` is created.
+    ///
+    /// Also, __**YOU MUST NOT USE IT IN OTHER LISTS/SPLICES SIMULTANEOUSLY OR
+    /// ADD IT TO THE SAME LIST SIMULTANEOUSLY
+    /// but you can REMOVE IT FROM THE SAME LIST SIMULTANEOUSLY**__.
+    pub unsafe fn new_unchecked(first: &'a Node, last: &'a Node) -> Self {
+        Self {
+            first_ptr: first as *const _ as *mut (),
+            last_ptr:  last  as *const _ as *mut (),
+            phantom: PhantomData,
+        }
+    }
+
+    pub fn new_empty() -> Self {
+        let null = ptr::null_mut();
+        Self {
+            first_ptr: null,
+            last_ptr:  null,
+            phantom: PhantomData,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.first_ptr.is_null()
+    }
+
+    /// # Safety
+    ///
+    ///  * `node` -  __**YOU MUST NOT USE IT IN OTHER LISTS/SPLICES SIMULTANEOUSLY OR
+    ///    ADD IT TO THE SAME LIST SIMULTANEOUSLY
+    ///    but you can REMOVE IT FROM THE SAME LIST SIMULTANEOUSLY**__.
+    pub unsafe fn push_front(&mut self, node: &'a Node) {
+        self.push_front_splice(Splice::new_unchecked(node, node))
+    }
+
+    pub fn push_front_splice(&mut self, splice: Self) {
+        use Ordering::Relaxed;
+
+        let last_node = unsafe { &*(splice.last_ptr as *mut Node as *const Node) };
+
+        let first = self.first_ptr;
+
+        last_node.get_next_ptr().store(first, Relaxed);
+
+        self.first_ptr = splice.first_ptr;
+        if first.is_null() {
+            self.last_ptr = splice.last_ptr;
+        } else {
+            let first = unsafe { &*(first as *mut Node as *const Node) };
+            first.get_prev_ptr().store(splice.last_ptr, Relaxed);
+        }
+    }
+
+    /// # Safety
+    ///
+    ///  * `node` -  __**YOU MUST NOT USE IT IN OTHER LISTS/SPLICES SIMULTANEOUSLY OR
+    ///    ADD IT TO THE SAME LIST SIMULTANEOUSLY
+    ///    but you can REMOVE IT FROM THE SAME LIST SIMULTANEOUSLY**__.
+    pub unsafe fn push_back(&mut self, node: &'a Node) {
+        self.push_back_splice(Splice::new_unchecked(node, node))
+    }
+
+    pub fn push_back_splice(&mut self, splice: Self) {
+        use Ordering::Relaxed;
+
+        let first_node = unsafe { &*(splice.first_ptr as *mut Node as *const Node) };
+
+        let last = self.last_ptr;
+
+        first_node.get_prev_ptr().store(last, Relaxed);
+
+        self.last_ptr = splice.last_ptr;
+        if last.is_null() {
+            self.first_ptr = splice.first_ptr;
+        } else {
+            let last = unsafe { &*(last as *mut Node as *const Node) };
+            last.get_next_ptr().store(splice.first_ptr, Relaxed);
+        }
+    }
+
+    /// Link the last node of `self` to the first node of `other` and
+    /// return the combined `Splice`.
+    ///
+    /// Either of `self` and `other` can be empty.
+    ///
+    /// # Safety
+    ///
+    ///  * `other` - must not share any node with `self`.
+    pub unsafe fn concat(mut self, other: Self) -> Self {
+        if !other.is_empty() {
+            self.push_back_splice(other);
+        }
+        self
+    }
+
+    /// Return the number of nodes in `self` by walking from the first node
+    /// to the last node.
+    pub fn count_nodes(&self) -> usize {
+        self.iter().count()
+    }
+
+    pub fn iter(&self) -> IntrusiveListIterator<'a, '_, Node> {
+        IntrusiveListIterator::from_splice(self)
+    }
+}
+impl<'a, Node: IntrusiveListNode<'a>>
+    From<Splice<'a, Node>> for Option<(&'a Node, &'a Node)>
+{
+    /// If `splice` is empty, then return value will be `None`.
+    fn from(splice: Splice<'a, Node>) -> Self {
+        if splice.is_empty() {
+            None
+        } else {
+            Some(unsafe {(
+                &* (splice.first_ptr as *mut Node as *const Node),
+                &* (splice.last_ptr  as *mut Node as *const Node),
+            )})
+        }
+    }
+}
+
+impl<'a, 'b, Node: IntrusiveListNode<'a>> IntoIterator for &'b Splice<'a, Node> {
+    type Item = &'a Node;
+    type IntoIter = IntrusiveListIterator<'a, 'b, Node>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Self::IntoIter::from_splice(self)
+    }
+}
+
+impl<'a, 'b, Node: IntrusiveListNode<'a>, P: OrderingPolicy>
+    IntoIterator for &'b IntrusiveList<'a, Node, P>
+{
+    type Item = &'a Node;
+    type IntoIter = IntrusiveListIterator<'a, 'b, Node, P>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Self::IntoIter::from_list(self)
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct IntrusiveListIterator<
+    'a,
+    'b,
+    Node: IntrusiveListNode<'a>,
+    P: OrderingPolicy = DefaultOrdering
+> {
+    first_ptr: * mut (),
+    last_ptr: *mut (),
+    phantom0: PhantomData<&'a Node>,
+    phantom1: PhantomData<&'b ()>,
+    policy: PhantomData<P>,
+}
+impl<'a, 'b, Node: IntrusiveListNode<'a>, P: OrderingPolicy> IntrusiveListIterator<'a, 'b, Node, P> {
+    pub(crate) fn from_list(list: &'b IntrusiveList<'a, Node, P>) -> Self {
+        loop {
+            let first_ptr = list.first_ptr.load(P::R_ORD);
+            let last_ptr  = list.last_ptr .load(P::R_ORD);
+
+            if (first_ptr.is_null() && last_ptr.is_null()) ||
+               ( (!first_ptr.is_null()) && (!last_ptr.is_null()) )
+            {
+                break Self {
+                    first_ptr,
+                    last_ptr,
+                    phantom0: PhantomData,
+                    phantom1: PhantomData,
+                    policy: PhantomData,
+                }
+            }
+
+            spin_wait();
+        }
+    }
+
+    pub(crate) fn from_splice(splice: &'b Splice<'a, Node>) -> Self {
+        Self {
+            first_ptr: splice.first_ptr,
+            last_ptr:  splice.last_ptr,
+            phantom0: PhantomData,
+            phantom1: PhantomData,
+            policy: PhantomData,
+        }
+    }
+}
+
+impl<'a, 'b, Node: IntrusiveListNode<'a>, P: OrderingPolicy>
+    Iterator for IntrusiveListIterator<'a, 'b, Node, P>
+{
+    type Item = &'a Node;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.first_ptr.is_null() {
+            return None;
+        }
+
+        let curr_node = unsafe { &* (self.first_ptr as *mut Node as *const Node) };
+
+        if self.first_ptr == self.last_ptr {
+            self.first_ptr = ptr::null_mut();
+            self.last_ptr = self.first_ptr;
+        } else {
+            self.first_ptr = curr_node.get_next_ptr().load(P::R_ORD);
+        }
+
+        Some(curr_node)
+    }
+
+    fn last(self) -> Option<Self::Item> {
+        if self.last_ptr.is_null() {
+            None
+        } else {
+            Some(unsafe { &* (self.last_ptr as *mut Node as *const Node) })
+        }
+    }
+}
+impl<'a, 'b, Node: IntrusiveListNode<'a>, P: OrderingPolicy>
+    DoubleEndedIterator for IntrusiveListIterator<'a, 'b, Node, P>
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.last_ptr.is_null() {
+            return None;
+        }
+
+        let curr_node = unsafe { &* (self.last_ptr as *mut Node as *const Node) };
+
+        if self.first_ptr == self.last_ptr {
+            self.first_ptr = ptr::null_mut();
+            self.last_ptr = self.first_ptr;
+        } else {
+            self.last_ptr = curr_node.get_prev_ptr().load(P::R_ORD);
+        }
+
+        Some(curr_node)
+    }
+}
+
+/// Iterator over elements of an `IntrusiveList` which can peek the next
+/// element without advancing, returned by `IntrusiveList::iter_peekable_elems`.
+pub struct PeekableElems<'a, 'b, Node: IntrusiveListNode<'a>, P: OrderingPolicy = DefaultOrdering> {
+    iter: iter::Peekable<IntrusiveListIterator<'a, 'b, Node, P>>,
+}
+impl<'a, 'b, Node: IntrusiveListNode<'a>, P: OrderingPolicy> PeekableElems<'a, 'b, Node, P> {
+    /// Return the next element without advancing the iterator.
+    pub fn peek(&mut self) -> Option<Node::Target> {
+        self.iter.peek().map(|node| node.get_elem())
+    }
+}
+impl<'a, 'b, Node: IntrusiveListNode<'a>, P: OrderingPolicy>
+    Iterator for PeekableElems<'a, 'b, Node, P>
+{
+    type Item = Node::Target;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|node| node.get_elem())
+    }
+}
+
+/// Iterator over nodes removed from an `IntrusiveList`, returned by
+/// `IntrusiveList::drain`.
+pub struct Drain<'a, Node: IntrusiveListNode<'a>> {
+    first_ptr: *mut (),
+    last_ptr: *mut (),
+    phantom: PhantomData<&'a Node>,
+}
+impl<'a, Node: IntrusiveListNode<'a>> Drain<'a, Node> {
+    fn unlink(&mut self, node: &'a Node) -> &'a Node {
+        use Ordering::Relaxed;
+
+        let null = ptr::null_mut();
+
+        if ptr::eq(self.first_ptr, self.last_ptr) {
+            self.first_ptr = null;
+            self.last_ptr  = null;
+        } else if ptr::eq(self.first_ptr, node as *const _ as *mut ()) {
+            self.first_ptr = node.get_next_ptr().load(Relaxed);
+        } else {
+            self.last_ptr = node.get_prev_ptr().load(Relaxed);
+        }
+
+        node.get_next_ptr().store(null, Relaxed);
+        node.get_prev_ptr().store(null, Relaxed);
+
+        node
+    }
+}
+impl<'a, Node: IntrusiveListNode<'a>> Iterator for Drain<'a, Node> {
+    type Item = &'a Node;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = unsafe { (self.first_ptr as *const Node).as_ref() }?;
+        Some(self.unlink(node))
+    }
+}
+impl<'a, Node: IntrusiveListNode<'a>> DoubleEndedIterator for Drain<'a, Node> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let node = unsafe { (self.last_ptr as *const Node).as_ref() }?;
+        Some(self.unlink(node))
+    }
+}
+impl<'a, Node: IntrusiveListNode<'a>> Drop for Drain<'a, Node> {
+    fn drop(&mut self) {
+        self.for_each(drop);
+    }
+}
+
+#[cfg(all(test, feature = "std", feature = "concurrency_toolkit"))]
+mod tests {
+    use super::*;
+    use assert_matches::assert_matches;
+    use more_asserts::assert_lt;
+
+    use concurrency_toolkit::sync::Arc;
+    use concurrency_toolkit::{spawn, join};
+
+    use once_cell::sync::Lazy;
+
+    type Node<T = usize> = IntrusiveListNodeImpl<T>;
+
+    fn setup() -> Vec<Node> {
+        (0..100).map(Node::new).collect()
+    }
+
+    fn setup_from(elems: &[usize]) -> Vec<Node> {
+        elems.iter().copied().map(Node::new).collect()
+    }
+
+    fn new_list(nodes: &[Node]) -> IntrusiveList<'_, Node> {
+        let list = IntrusiveList::new();
+        for node in nodes {
+            unsafe { list.push_back(node) };
+        }
+        list
+    }
+
+    fn elems(list: &IntrusiveList<'_, Node>) -> Vec<usize> {
+        list.iter().map(|node| *node.get_elem()).collect()
+    }
+
+    fn rev_elems(list: &IntrusiveList<'_, Node>) -> Vec<usize> {
+        list.iter().rev().map(|node| *node.get_elem()).collect()
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_splice_empty() {
+        let splice: Splice<'_, Node> = Default::default();
+
+        assert!(splice.is_empty());
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_splice_push_back_splice() {
+        let nodes = setup();
+
+        let mut splice0: Splice<'_, _> = Default::default();
+
+        for node in &nodes[0..50] {
+            unsafe { splice0.push_back(node) };
+            assert!(!splice0.is_empty());
+        }
+
+        for (index, node) in splice0.iter().enumerate() {
+            assert_eq!(index, *node.get_elem());
+            assert_lt!(index, 50);
+        }
+
+        let mut splice1: Splice<'_, _> = Default::default();
+
+        for node in &nodes[50..100] {
+            unsafe { splice1.push_back(node) };
+            assert!(!splice1.is_empty());
+        }
+
+        for (index, node) in splice1.iter().enumerate() {
+            assert_eq!(index + 50, *node.get_elem());
+            assert_lt!(index, 50);
+        }
+
+        splice0.push_back_splice(splice1);
+
+        for (index, node) in splice0.iter().enumerate() {
+            assert_eq!(index, *node.get_elem());
+            assert_lt!(index, 100);
+        }
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_splice_push_back_and_push_front() {
+        let nodes = setup();
+
+        let mut splice: Splice<'_, _> = Default::default();
+
+        // Test push_back + push_front + next
+        for node in &nodes {
+            if *node.get_elem() % 2 == 0 {
+                unsafe { splice.push_back(node) };
+            } else {
+                unsafe { splice.push_front(node) };
+            }
+
+            assert!(!splice.is_empty());
+        }
+
+        let mut iter = splice.iter();
+
+        for (index, node) in (1..100).rev().step_by(2).zip(&mut iter) {
+            assert_lt!(index, 100);
+            assert_eq!(index % 2, 1);
+            assert_eq!(index, *node.get_elem());
+        }
+        assert!(!splice.is_empty());
+
+        for (index, node) in (0..100).step_by(2).zip(iter) {
+            assert_lt!(index, 100);
+            assert_eq!(index % 2, 0);
+            assert_eq!(index, *node.get_elem());
+        }
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_splice_push_back_splice_and_push_front_splice() {
+        let nodes = setup();
+
+        let mut splice0: Splice<'_, _> = Default::default();
+        let mut splice1: Splice<'_, _> = Default::default();
+
+        for node in &nodes {
+            if *node.get_elem() % 2 == 0 {
+                unsafe { splice0.push_back(node) };
+                assert!(!splice0.is_empty());
+            } else {
+                unsafe { splice1.push_front(node) };
+                assert!(!splice1.is_empty());
+            }
+        }
+
+        let mut splice:  Splice<'_, _> = Default::default();
+        splice.push_back_splice(splice0);
+        splice.push_front_splice(splice1);
+
+        let mut iter = splice.iter();
+
+        for (index, node) in (1..100).rev().step_by(2).zip(&mut iter) {
+            assert_lt!(index, 100);
+            assert_eq!(index % 2, 1);
+            assert_eq!(index, *node.get_elem());
+        }
+        assert!(!splice.is_empty());
+
+        for (index, node) in (0..100).step_by(2).zip(iter) {
+            assert_lt!(index, 100);
+            assert_eq!(index % 2, 0);
+            assert_eq!(index, *node.get_elem());
+        }
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_iterator() {
+        let nodes = setup();
+
+        let mut splice: Splice<'_, _> = Default::default();
+ 
+        assert_matches!(splice.iter().next(), None);
+        assert_matches!(splice.iter().last(), None);
+        assert_matches!(splice.iter().next_back(), None);
+
+        for node in &nodes {
+            unsafe { splice.push_back(node) };
+            assert!(!splice.is_empty());
+        }
+
+        assert_matches!(splice.iter().next(), Some(node) if *node.get_elem() == 0);
+        assert_matches!(splice.iter().last(), Some(node) if *node.get_elem() == 99);
+        assert_matches!(splice.iter().next_back(), Some(node) if *node.get_elem() == 99);
+
+        for (node, index) in splice.iter().rev().zip((0..100).rev()) {
+            assert_eq!(index, *node.get_elem());
+        }
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_push_back_splice() {
+        let nodes = setup();
+
+        let list = IntrusiveList::new();
+        eprintln!("list = {:#?}", list);
+
+        for node in &nodes[0..50] {
+            unsafe { list.push_back(node) };
+            assert!(!list.is_empty());
+        }
+
+        for (index, node) in list.iter().enumerate() {
+            assert_eq!(index, *node.get_elem());
+            assert_lt!(index, 50);
+        }
+
+        let mut splice: Splice<'_, _> = Default::default();
+
+        for node in &nodes[50..100] {
+            unsafe { splice.push_back(node) };
+            assert!(!splice.is_empty());
+        }
+
+        for (index, node) in splice.iter().enumerate() {
+            assert_eq!(index + 50, *node.get_elem());
+            assert_lt!(index, 50);
+        }
+
+        list.push_back_splice(splice);
+
+        for (index, node) in list.iter().enumerate() {
+            assert_eq!(index, *node.get_elem());
+            assert_lt!(index, 100);
+        }
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_push_back_splice_and_push_front_splice() {
+        let nodes = setup();
+
+        let mut splice0: Splice<'_, _> = Default::default();
+        let mut splice1: Splice<'_, _> = Default::default();
+
+        for node in &nodes {
+            if *node.get_elem() % 2 == 0 {
+                unsafe { splice0.push_back(node) };
+                assert!(!splice0.is_empty());
+            } else {
+                unsafe { splice1.push_front(node) };
+                assert!(!splice1.is_empty());
+            }
+        }
+
+        let list = IntrusiveList::new();
+        list.push_back_splice(splice0);
+        list.push_front_splice(splice1);
+        assert!(!list.is_empty());
+
+        let mut iter = list.iter();
+
+        for (index, node) in (1..100).rev().step_by(2).zip(&mut iter) {
+            assert_lt!(index, 100);
+            assert_eq!(index % 2, 1);
+            assert_eq!(index, *node.get_elem());
+        }
+        assert!(!list.is_empty());
+
+        for (index, node) in (0..100).step_by(2).zip(iter) {
+            assert_lt!(index, 100);
+            assert_eq!(index % 2, 0);
+            assert_eq!(index, *node.get_elem());
+        }
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_clear() {
+        let nodes = setup();
+
+        let mut list = IntrusiveList::new();
+
+        for node in &nodes[0..50] {
+            unsafe { list.push_back(node) };
+        }
+        assert!(!list.is_empty());
+
+        list.clear();
+        assert!(list.is_empty());
+
+        assert_eq!("[]", format!("{:#?}", list));
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_splice() {
+        let nodes = setup();
+
+        let mut list = IntrusiveList::new();
+
+        for node in &nodes {
+            unsafe { list.push_back(node) };
+        }
+        assert!(!list.is_empty());
+
+        let first = list.iter().nth(50).unwrap();
+        let last  = list.iter().last().unwrap();
+
+        for (index, node) in unsafe {
+            list.splice(first, last).unwrap()
+        }.iter().enumerate() {
+            assert_eq!(index + 50, *node.get_elem());
+            assert_lt!(index, 50);
+        }
+
+        for (index, node) in list.iter().enumerate() {
+            assert_eq!(index, *node.get_elem());
+            assert_lt!(index, 50);
+        }
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_remove_if() {
+        let nodes = setup();
+
+        let mut list = IntrusiveList::new();
+
+        for node in &nodes {
+            unsafe { list.push_back(node) };
+        }
+        assert!(!list.is_empty());
+
+        assert_eq!((50, 50), list.remove_if(|node| *node.get_elem() % 2 == 1));
+
+        for (index, node) in (0..100).step_by(2).zip(&list) {
+            assert_eq!(index, *node.get_elem());
+        }
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_remove_if_runs() {
+        let nodes = setup_from(&[1, 2, 3, 4, 5, 6, 7, 8]);
+        let mut list = new_list(&nodes);
+
+        // suffix
+        assert_eq!((6, 2), list.remove_if(|node| *node.get_elem() > 6));
+        assert_eq!(vec![1, 2, 3, 4, 5, 6], elems(&list));
+        assert_eq!(vec![6, 5, 4, 3, 2, 1], rev_elems(&list));
+
+        // prefix
+        assert_eq!((4, 2), list.remove_if(|node| *node.get_elem() < 3));
+        assert_eq!(vec![3, 4, 5, 6], elems(&list));
+        assert_eq!(vec![6, 5, 4, 3], rev_elems(&list));
+
+        // interior
+        assert_eq!((2, 2), list.remove_if(|node| matches!(*node.get_elem(), 4 | 5)));
+        assert_eq!(vec![3, 6], elems(&list));
+        assert_eq!(vec![6, 3], rev_elems(&list));
+
+        assert!(list.link_report().consistent);
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_push_back_splice_concurrent() {
+        static NODES: Lazy<Vec<Node>> = Lazy::new(setup);
+        static LIST: Lazy<IntrusiveList<'static, Node>> = Lazy::new(IntrusiveList::new);
+
+        let mut splice0: Splice<'_, _> = Default::default();
+        let mut splice1: Splice<'_, _> = Default::default();
+
+        let nodes = &*NODES;
+
+        for node in &nodes[0..50] {
+            unsafe { splice0.push_back(node) };
+        }
+
+        for node in &nodes[50..100] {
+            unsafe { splice1.push_back(node) };
+        }
+
+        let handle0 = spawn!({
+            LIST.push_back_splice(splice0);
+        });
+
+        let handle1 = spawn!({
+            LIST.push_back_splice(splice1);
+        });
+
+        join!(handle0).unwrap();
+        join!(handle1).unwrap();
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_longest_sorted_run() {
+        let nodes = setup_from(&[1, 2, 3, 1, 2]);
+
+        let list = new_list(&nodes[..0]);
+        assert_matches!(list.longest_sorted_run(|x, y| x.get_elem() < y.get_elem()), None);
+
+        let list = new_list(&nodes);
+        let (first, last) = list
+            .longest_sorted_run(|x, y| x.get_elem() < y.get_elem())
+            .unwrap();
+        assert!(ptr::eq(first, &nodes[0]));
+        assert!(ptr::eq(last,  &nodes[2]));
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_splice_concat() {
+        let nodes = setup();
+
+        let mut splice0: Splice<'_, _> = Default::default();
+        let mut splice1: Splice<'_, _> = Default::default();
+
+        for node in &nodes[0..50] {
+            unsafe { splice0.push_back(node) };
+        }
+        for node in &nodes[50..100] {
+            unsafe { splice1.push_back(node) };
+        }
+
+        let splice = unsafe { splice0.concat(splice1) };
+        let splice = unsafe { splice.concat(Default::default()) };
+        let splice = unsafe { Splice::new_empty().concat(splice) };
+
+        for (index, node) in splice.iter().enumerate() {
+            assert_eq!(index, *node.get_elem());
+            assert_lt!(index, 100);
+        }
+        assert_eq!(100, splice.iter().count());
+        assert_eq!(100, splice.iter().rev().count());
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_iter_by_address() {
+        let nodes = setup();
+
+        let list = IntrusiveList::new();
+        for node in &nodes {
+            unsafe { list.push_front(node) };
+        }
+
+        let sorted: Vec<_> = list.iter_by_address().collect();
+        assert_eq!(100, sorted.len());
+
+        for pair in sorted.windows(2) {
+            assert_lt!(pair[0] as *const Node, pair[1] as *const Node);
+        }
+        for node in &list {
+            assert!(sorted.iter().any(|sorted_node| ptr::eq(*sorted_node, node)));
+        }
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_is_sorted_by() {
+        let less = |x: &Node, y: &Node| x.get_elem() < y.get_elem();
+
+        let nodes = setup_from(&[1, 2, 2, 3]);
+        assert!(new_list(&nodes).is_sorted_by(less));
+
+        let nodes = setup_from(&[1, 3, 2]);
+        assert!(!new_list(&nodes).is_sorted_by(less));
+
+        let nodes = setup_from(&[]);
+        assert!(new_list(&nodes).is_sorted_by(less));
+
+        let nodes = setup_from(&[1]);
+        assert!(new_list(&nodes).is_sorted_by(less));
+    }
+
+    #[derive(Debug)]
+    struct KeyedNodeImpl {
+        node: Node<&'static str>,
+        key: usize,
+    }
+    unsafe impl<'a> IntrusiveForwardListNode<'a> for KeyedNodeImpl {
+        type Target = &'static str;
+
+        fn get_next_ptr(&self) -> &AtomicPtr<()> {
+            self.node.get_next_ptr()
+        }
+        fn get_elem(&'a self) -> Self::Target {
+            *self.node.get_elem()
+        }
+    }
+    unsafe impl<'a> IntrusiveListNode<'a> for KeyedNodeImpl {
+        fn get_prev_ptr(&self) -> &AtomicPtr<()> {
+            self.node.get_prev_ptr()
+        }
+    }
+    impl<'a> KeyedNode<'a> for KeyedNodeImpl {
+        type Key = usize;
+
+        fn get_key(&self) -> &Self::Key {
+            &self.key
+        }
+    }
+
+    struct TaggedNodeImpl {
+        node: Node,
+        tag: AtomicPtr<()>,
+    }
+    unsafe impl<'a> IntrusiveForwardListNode<'a> for TaggedNodeImpl {
+        type Target = usize;
+
+        fn get_next_ptr(&self) -> &AtomicPtr<()> {
+            self.node.get_next_ptr()
+        }
+        fn get_elem(&'a self) -> Self::Target {
+            *self.node.get_elem()
+        }
+    }
+    unsafe impl<'a> IntrusiveListNode<'a> for TaggedNodeImpl {
+        fn get_prev_ptr(&self) -> &AtomicPtr<()> {
+            self.node.get_prev_ptr()
+        }
+    }
+    impl<'a> TaggedNode<'a> for TaggedNodeImpl {
+        fn get_list_tag(&self) -> &AtomicPtr<()> {
+            &self.tag
+        }
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_to_btree_map() {
+        let nodes: Vec<_> = [(2, "b"), (1, "a"), (3, "c"), (2, "d")]
+            .iter()
+            .map(|(key, elem)| KeyedNodeImpl { node: Node::new(*elem), key: *key })
+            .collect();
+
+        let list = IntrusiveList::new();
+        for node in &nodes {
+            unsafe { list.push_back(node) };
+        }
+
+        let map = list.to_btree_map();
+        assert_eq!(
+            vec![(1, "a"), (2, "d"), (3, "c")],
+            map.into_iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_remove_all_eq() {
+        let nodes = setup_from(&[1, 2, 2, 3, 2]);
+        let mut list = new_list(&nodes);
+
+        assert_eq!(3, list.remove_all_eq(&&2));
+        assert_eq!(vec![1, 3], elems(&list));
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_iter_unique() {
+        let nodes = setup_from(&[1, 2, 1, 3, 2]);
+        let list = new_list(&nodes);
+
+        let unique: Vec<_> = list.iter_unique().collect();
+        assert_eq!(3, unique.len());
+        assert!(ptr::eq(unique[0], &nodes[0]));
+        assert!(ptr::eq(unique[1], &nodes[1]));
+        assert!(ptr::eq(unique[2], &nodes[3]));
+
+        assert_eq!(vec![1, 2, 1, 3, 2], elems(&list));
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_replace_all() {
+        let nodes = setup_from(&[0, 1, 2, 3, 4]);
+        let mut list = new_list(&nodes[0..2]);
+
+        let new_nodes: Vec<_> = nodes[2..5].iter().collect();
+        let old_nodes = unsafe { list.replace_all(&new_nodes) };
+
+        assert_eq!(2, old_nodes.len());
+        assert!(ptr::eq(old_nodes[0], &nodes[0]));
+        assert!(ptr::eq(old_nodes[1], &nodes[1]));
+
+        assert_eq!(vec![2, 3, 4], elems(&list));
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_min_max_elem_node() {
+        let nodes = setup_from(&[3, 1, 4, 1, 5, 9, 2, 9]);
+        let list = new_list(&nodes);
+
+        assert!(ptr::eq(list.min_elem_node().unwrap(), &nodes[1]));
+        assert!(ptr::eq(list.max_elem_node().unwrap(), &nodes[5]));
+
+        let list = new_list(&nodes[..0]);
+        assert_matches!(list.min_elem_node(), None);
+        assert_matches!(list.max_elem_node(), None);
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_move_matching_to_front() {
+        let nodes = setup_from(&[1, 2, 3, 4, 5, 6]);
+        let mut list = new_list(&nodes);
+
+        list.move_matching_to_front(|node| *node.get_elem() % 2 == 0);
+        assert_eq!(vec![2, 4, 6, 1, 3, 5], elems(&list));
+        assert_eq!(vec![5, 3, 1, 6, 4, 2], rev_elems(&list));
+
+        list.move_matching_to_front(|_| false);
+        assert_eq!(vec![2, 4, 6, 1, 3, 5], elems(&list));
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_iter_with_next_elem() {
+        let nodes = setup_from(&[1, 2, 3]);
+        let list = new_list(&nodes);
+
+        let pairs: Vec<_> = list
+            .iter_with_next_elem()
+            .map(|(node, next)| (*node.get_elem(), next.copied()))
+            .collect();
+        assert_eq!(vec![(1, Some(2)), (2, Some(3)), (3, None)], pairs);
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_scan_elems() {
+        let nodes = setup_from(&[1, 2, 3, 4]);
+        let list = new_list(&nodes);
+
+        assert_eq!(vec![1, 3, 6, 10], list.scan_elems(0, |sum, node| sum + node.elem));
+        assert!(new_list(&nodes[..0]).scan_elems(0, |sum, node| sum + node.elem).is_empty());
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_rotate_by() {
+        let nodes = setup_from(&[1, 2, 3, 4]);
+        let mut list = new_list(&nodes);
+
+        list.rotate_by(|len| len / 2);
+        assert_eq!(vec![3, 4, 1, 2], elems(&list));
+
+        list.rotate_by(|len| len * 2);
+        assert_eq!(vec![3, 4, 1, 2], elems(&list));
+
+        list.rotate_by(|len| len + 1);
+        assert_eq!(vec![4, 1, 2, 3], elems(&list));
+
+        let mut list = new_list(&nodes[..0]);
+        list.rotate_by(|len| {
+            assert_eq!(0, len);
+            1
+        });
+        assert!(list.is_empty());
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_iter_filter() {
+        let nodes = setup_from(&[1, 2, 3, 4, 5]);
+        let list = new_list(&nodes);
+
+        let odd: Vec<_> = list
+            .iter_filter(|node| *node.get_elem() % 2 == 1)
+            .map(|node| *node.get_elem())
+            .collect();
+        assert_eq!(vec![1, 3, 5], odd);
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_splice_count_nodes() {
+        let nodes = setup();
+
+        let mut splice: Splice<'_, _> = Default::default();
+        assert_eq!(0, splice.count_nodes());
+
+        for node in &nodes[0..42] {
+            unsafe { splice.push_back(node) };
+        }
+        assert_eq!(42, splice.count_nodes());
+
+        let mut list = new_list(&nodes[42..100]);
+        let first = list.iter().nth(10).unwrap();
+        let last  = list.iter().nth(19).unwrap();
+        let splice = unsafe { list.splice(first, last) }.unwrap();
+        assert_eq!(10, splice.count_nodes());
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_consuming_iter() {
+        use Ordering::Relaxed;
+
+        let nodes = setup_from(&[1, 2, 3, 4, 5]);
+        let mut list = new_list(&nodes);
+
+        let consumed: Vec<_> = list.consuming_iter().take(2).collect();
+        assert_eq!(2, consumed.len());
+        for (node, consumed) in nodes.iter().zip(consumed) {
+            assert!(ptr::eq(node, consumed));
+            assert!(node.get_next_ptr().load(Relaxed).is_null());
+            assert!(node.get_prev_ptr().load(Relaxed).is_null());
+        }
+        assert_eq!(vec![3, 4, 5], elems(&list));
+
+        assert_eq!(3, list.consuming_iter().count());
+        assert!(list.is_empty());
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_promote() {
+        let nodes = setup_from(&[1, 2, 3, 4]);
+        let mut list = new_list(&nodes);
+
+        assert!(unsafe { list.promote(&nodes[2]) });
+        assert_eq!(vec![1, 3, 2, 4], elems(&list));
+
+        assert!(unsafe { list.promote(&nodes[1]) });
+        assert_eq!(vec![1, 2, 3, 4], elems(&list));
+
+        assert!(unsafe { list.promote(&nodes[1]) });
+        assert_eq!(vec![2, 1, 3, 4], elems(&list));
+
+        assert!(!unsafe { list.promote(&nodes[1]) });
+        assert_eq!(vec![2, 1, 3, 4], elems(&list));
+
+        assert!(unsafe { list.promote(&nodes[3]) });
+        assert_eq!(vec![2, 1, 4, 3], elems(&list));
+        assert_eq!(vec![3, 4, 1, 2], rev_elems(&list));
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_demote() {
+        let nodes = setup_from(&[1, 2, 3, 4]);
+        let mut list = new_list(&nodes);
+
+        assert!(unsafe { list.demote(&nodes[1]) });
+        assert_eq!(vec![1, 3, 2, 4], elems(&list));
+
+        assert!(unsafe { list.demote(&nodes[1]) });
+        assert_eq!(vec![1, 3, 4, 2], elems(&list));
+
+        assert!(!unsafe { list.demote(&nodes[1]) });
+        assert_eq!(vec![1, 3, 4, 2], elems(&list));
+
+        assert!(unsafe { list.demote(&nodes[0]) });
+        assert_eq!(vec![3, 1, 4, 2], elems(&list));
+        assert_eq!(vec![2, 4, 1, 3], rev_elems(&list));
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_snapshot_rev() {
+        let nodes = setup();
+        let list = new_list(&nodes);
+
+        let mut snapshot: Vec<_> = list.iter().map(|node| node.get_elem()).collect();
+        snapshot.reverse();
+        assert_eq!(snapshot, list.snapshot_rev());
+
+        assert!(new_list(&nodes[..0]).snapshot_rev().is_empty());
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_find_invalid() {
+        let nodes = setup_from(&[1, 5, 200, 7, 300]);
+        let list = new_list(&nodes);
+
+        let invalid = list.find_invalid(|node| *node.get_elem() < 100).unwrap();
+        assert!(ptr::eq(invalid, &nodes[2]));
+
+        assert_matches!(list.find_invalid(|node| *node.get_elem() < 1000), None);
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_clear_if() {
+        let nodes = setup_from(&[1, 2, 3]);
+        let mut list = new_list(&nodes);
+
+        assert!(!list.clear_if(|len| len > 3));
+        assert_eq!(vec![1, 2, 3], elems(&list));
+
+        assert!(list.clear_if(|len| len >= 3));
+        assert!(list.is_empty());
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_iter_debug() {
+        let nodes = setup_from(&[1, 2, 3, 4]);
+        let list = new_list(&nodes);
+
+        let links: Vec<_> = list.iter_debug().collect();
+        assert_eq!(4, links.len());
+
+        assert!(links[0].1.is_null());
+        assert!(links[3].2.is_null());
+        for (node, (addr, _, _)) in nodes.iter().zip(&links) {
+            assert_eq!(node as *const _ as *mut (), *addr);
+        }
+        for pair in links.windows(2) {
+            assert_eq!(pair[0].2, pair[1].0);
+            assert_eq!(pair[1].1, pair[0].0);
+        }
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_pop_front_or_insert() {
+        let nodes = setup_from(&[1, 2, 3]);
+
+        let mut list = new_list(&nodes[0..2]);
+        let node = unsafe { list.pop_front_or_insert(|| unreachable!()) };
+        assert!(ptr::eq(node, &nodes[0]));
+        assert_eq!(vec![2], elems(&list));
+
+        let mut list = new_list(&nodes[..0]);
+        let node = unsafe { list.pop_front_or_insert(|| &nodes[2]) };
+        assert!(ptr::eq(node, &nodes[2]));
+        assert_eq!(vec![3], elems(&list));
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_pop_back_or_insert() {
+        let nodes = setup_from(&[1, 2, 3]);
+
+        let mut list = new_list(&nodes[0..2]);
+        let node = unsafe { list.pop_back_or_insert(|| unreachable!()) };
+        assert!(ptr::eq(node, &nodes[1]));
+        assert_eq!(vec![1], elems(&list));
+        assert_eq!(vec![1], rev_elems(&list));
+
+        let mut list = new_list(&nodes[..0]);
+        let node = unsafe { list.pop_back_or_insert(|| &nodes[2]) };
+        assert!(ptr::eq(node, &nodes[2]));
+        assert_eq!(vec![3], elems(&list));
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_are_adjacent() {
+        let nodes = setup_from(&[1, 2, 3]);
+        let list = new_list(&nodes);
+
+        assert!(unsafe { list.are_adjacent(&nodes[0], &nodes[1]) });
+        assert!(unsafe { list.are_adjacent(&nodes[1], &nodes[2]) });
+        assert!(!unsafe { list.are_adjacent(&nodes[1], &nodes[0]) });
+        assert!(!unsafe { list.are_adjacent(&nodes[0], &nodes[2]) });
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_reverse() {
+        let nodes = setup();
+
+        let mut list = new_list(&nodes[..0]);
+        list.reverse();
+        assert!(list.is_empty());
+
+        let nodes = setup();
+        let mut list = new_list(&nodes[..1]);
+        list.reverse();
+        assert_eq!(vec![0], elems(&list));
+        assert_eq!(vec![0], rev_elems(&list));
+
+        let nodes = setup();
+        let mut list = new_list(&nodes[..2]);
+        list.reverse();
+        assert_eq!(vec![1, 0], elems(&list));
+        assert_eq!(vec![0, 1], rev_elems(&list));
+
+        let nodes = setup();
+        let mut list = new_list(&nodes);
+        list.reverse();
+        assert_eq!((0..100).rev().collect::<Vec<_>>(), elems(&list));
+        assert_eq!((0..100).collect::<Vec<_>>(), rev_elems(&list));
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_group_to_map() {
+        let nodes = setup_from(&[1, 2, 3, 4, 5]);
+        let list = new_list(&nodes);
+
+        let map = list.group_to_map(|node| *node.get_elem() % 2 == 0);
+        assert_eq!(2, map.len());
+        assert_eq!(vec![&2, &4], map[&true]);
+        assert_eq!(vec![&1, &3, &5], map[&false]);
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_push_back_bounded_evict() {
+        let nodes = setup_from(&[1, 2, 3, 4, 5]);
+        let mut list = IntrusiveList::new();
+
+        for node in &nodes[0..3] {
+            assert_matches!(unsafe { list.push_back_bounded_evict(node, 3) }, None);
+        }
+        assert_eq!(vec![1, 2, 3], elems(&list));
+
+        let evicted = unsafe { list.push_back_bounded_evict(&nodes[3], 3) }.unwrap();
+        assert!(ptr::eq(evicted, &nodes[0]));
+        assert_eq!(vec![2, 3, 4], elems(&list));
+
+        let evicted = unsafe { list.push_back_bounded_evict(&nodes[4], 3) }.unwrap();
+        assert!(ptr::eq(evicted, &nodes[1]));
+        assert_eq!(vec![3, 4, 5], elems(&list));
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_extract_between() {
+        let nodes = setup_from(&[1, 2, 3, 4, 5]);
+
+        let mut list = new_list(&nodes);
+        let extracted = list
+            .extract_between(|node| *node.get_elem() == 2, |node| *node.get_elem() == 4)
+            .unwrap();
+        assert_eq!(vec![2, 3, 4], elems(&extracted));
+        assert_eq!(vec![1, 5], elems(&list));
+        assert_eq!(vec![5, 1], rev_elems(&list));
+
+        let nodes = setup_from(&[1, 2, 3, 4, 5]);
+        let mut list = new_list(&nodes);
+        assert_matches!(
+            list.extract_between(|node| *node.get_elem() == 2, |node| *node.get_elem() == 6),
+            None
+        );
+        assert_matches!(
+            list.extract_between(|node| *node.get_elem() == 4, |node| *node.get_elem() == 2),
+            None
+        );
+        assert_eq!(vec![1, 2, 3, 4, 5], elems(&list));
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_rfind() {
+        let nodes = setup_from(&[1, 2, 3, 2, 5]);
+        let list = new_list(&nodes);
+
+        let found = list.rfind(|node| *node.get_elem() == 2).unwrap();
+        assert!(ptr::eq(found, &nodes[3]));
+        assert!(!ptr::eq(found, list.iter().find(|node| *node.get_elem() == 2).unwrap()));
+
+        assert_matches!(list.rfind(|node| *node.get_elem() == 4), None);
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_index_of() {
+        for len in [98, 99] {
+            let nodes = setup();
+            let list = new_list(&nodes[..len]);
+
+            for (index, node) in nodes[..len].iter().enumerate() {
+                assert_eq!(Some(index), list.index_of(node));
+            }
+            assert_eq!(None, list.index_of(&nodes[99]));
+        }
+
+        let nodes = setup();
+        let list = new_list(&nodes[..0]);
+        assert_eq!(None, list.index_of(&nodes[0]));
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_set_next() {
+        use Ordering::Relaxed;
+
+        let nodes = setup_from(&[1, 2, 3, 4, 5, 6]);
+        let mut list = new_list(&nodes[..5]);
+
+        unsafe { list.set_next(&nodes[1], Some(&nodes[4])) };
+        assert_eq!(vec![1, 2, 5], elems(&list));
+        assert_eq!(vec![5, 2, 1], rev_elems(&list));
+        for node in &nodes[2..4] {
+            assert!(node.get_prev_ptr().load(Relaxed).is_null());
+            assert!(node.get_next_ptr().load(Relaxed).is_null());
+        }
+
+        unsafe { list.set_next(&nodes[4], Some(&nodes[5])) };
+        assert_eq!(vec![1, 2, 5, 6], elems(&list));
+        assert_eq!(vec![6, 5, 2, 1], rev_elems(&list));
+
+        unsafe { list.set_next(&nodes[1], None) };
+        assert_eq!(vec![1, 2], elems(&list));
+        assert_eq!(vec![2, 1], rev_elems(&list));
+        for node in &nodes[4..] {
+            assert!(node.get_prev_ptr().load(Relaxed).is_null());
+            assert!(node.get_next_ptr().load(Relaxed).is_null());
+        }
+
+        // Dropped nodes can be pushed again.
+        unsafe {
+            list.push_back(&nodes[2]);
+            list.push_back(&nodes[5]);
+        }
+        assert_eq!(vec![1, 2, 3, 6], elems(&list));
+        assert_eq!(vec![6, 3, 2, 1], rev_elems(&list));
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_partition3() {
+        let nodes = setup_from(&[3, 5, 1, 3, 4, 2, 3]);
+        let mut list = new_list(&nodes);
+
+        let (less, greater) = list.partition3(|node| node.get_elem().cmp(&3));
+        assert_eq!(vec![1, 2], elems(&less));
+        assert_eq!(vec![2, 1], rev_elems(&less));
+        assert_eq!(vec![5, 4], elems(&greater));
+        assert_eq!(vec![4, 5], rev_elems(&greater));
+        assert_eq!(vec![3, 3, 3], elems(&list));
+        assert_eq!(vec![3, 3, 3], rev_elems(&list));
+    }
+
+    /// Node whose element is returned by value.
+    struct ValueNode(Node);
+
+    unsafe impl<'a> IntrusiveForwardListNode<'a> for ValueNode {
+        type Target = usize;
+
+        fn get_next_ptr(&self) -> &AtomicPtr<()> {
+            self.0.get_next_ptr()
+        }
+        fn get_elem(&'a self) -> Self::Target {
+            *self.0.get_elem()
+        }
+    }
+    unsafe impl<'a> IntrusiveListNode<'a> for ValueNode {
+        fn get_prev_ptr(&self) -> &AtomicPtr<()> {
+            self.0.get_prev_ptr()
+        }
+    }
+
+    fn setup_values(elems: &[usize]) -> Vec<ValueNode> {
+        elems.iter().copied().map(|elem| ValueNode(Node::new(elem))).collect()
+    }
+
+    fn new_value_list(nodes: &[ValueNode]) -> IntrusiveList<'_, ValueNode> {
+        let list = IntrusiveList::new();
+        for node in nodes {
+            unsafe { list.push_back(node) };
+        }
+        list
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_iter_until_sum() {
+        let nodes = setup_values(&[3, 3, 3]);
+        let list = new_value_list(&nodes);
+
+        let taken: Vec<_> = list.iter_until_sum(7, |x, y| x + y).collect();
+        assert_eq!(2, taken.len());
+        assert!(ptr::eq(taken[0], &nodes[0]));
+        assert!(ptr::eq(taken[1], &nodes[1]));
+
+        assert_eq!(0, list.iter_until_sum(3, |x, y| x + y).count());
+        assert_eq!(3, list.iter_until_sum(10, |x, y| x + y).count());
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_node_swap_elems() {
+        let nodes: Vec<Node<UnsafeCell<usize>>> = (0..3)
+            .map(|elem| Node::new(UnsafeCell::new(elem)))
+            .collect();
+
+        let list = IntrusiveList::new();
+        for node in &nodes {
+            unsafe { list.push_back(node) };
+        }
+
+        unsafe {
+            IntrusiveListNodeMut::swap_elems(&nodes[0], &nodes[2]);
+            IntrusiveListNodeMut::swap_elems(&nodes[1], &nodes[1]);
+        }
+
+        for (node, expected) in nodes.iter().zip(&list) {
+            assert!(ptr::eq(node, expected));
+        }
+        let elems: Vec<_> = list.iter().map(|node| unsafe { *node.get_elem_mut() }).collect();
+        assert_eq!(vec![2, 1, 0], elems);
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_to_array() {
+        let nodes = setup_from(&[1, 2, 3]);
+        let list = new_list(&nodes);
+
+        assert_eq!(Some([&1, &2, &3]), list.to_array::<3>());
+        assert_eq!(None, list.to_array::<4>());
+        assert_eq!(None, list.to_array::<2>());
+        assert_eq!(Some([]), new_list(&nodes[..0]).to_array::<0>());
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_first_divergence() {
+        let nodes0 = setup_from(&[1, 2, 3, 4]);
+        let nodes1 = setup_from(&[1, 2, 3, 4]);
+        let nodes2 = setup_from(&[1, 2, 5, 4]);
+        let nodes3 = setup_from(&[1, 2]);
+        let nodes4 = setup_from(&[1, 2]);
+
+        let list0 = new_list(&nodes0);
+
+        assert_eq!(None, list0.first_divergence(&new_list(&nodes1)));
+        assert_eq!(None, list0.first_divergence(&new_list(&nodes3)));
+        assert_eq!(None, new_list(&nodes4).first_divergence(&list0));
+        assert_eq!(Some(2), list0.first_divergence(&new_list(&nodes2)));
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_insertion_sort_step() {
+        let less = |x: &Node, y: &Node| x.get_elem() < y.get_elem();
+
+        let nodes = setup_from(&[5, 2, 4, 6, 1, 3, 2]);
+        let mut list = new_list(&nodes);
+
+        assert!(list.insertion_sort_step(less));
+        assert_eq!(vec![2, 5, 4, 6, 1, 3, 2], elems(&list));
+
+        while list.insertion_sort_step(less) {}
+        assert_eq!(vec![1, 2, 2, 3, 4, 5, 6], elems(&list));
+        assert_eq!(vec![6, 5, 4, 3, 2, 2, 1], rev_elems(&list));
+
+        // Sorting is stable
+        assert!(ptr::eq(list.iter().nth(1).unwrap(), &nodes[1]));
+        assert!(ptr::eq(list.iter().nth(2).unwrap(), &nodes[6]));
+
+        assert!(!new_list(&nodes[..0]).insertion_sort_step(less));
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_push_back_evict_if() {
+        // Elements are timestamps of the nodes
+        let nodes = setup_from(&[10, 15, 30]);
+        let ttl = 10;
+
+        let mut list = IntrusiveList::new();
+
+        let now = 15;
+        let expired = |node: &Node| *node.get_elem() + ttl < now;
+        assert_matches!(unsafe { list.push_back_evict_if(&nodes[0], expired) }, None);
+        assert_matches!(unsafe { list.push_back_evict_if(&nodes[1], expired) }, None);
+
+        let now = 30;
+        let expired = |node: &Node| *node.get_elem() + ttl < now;
+        let evicted = unsafe { list.push_back_evict_if(&nodes[2], expired) }.unwrap();
+        assert!(ptr::eq(evicted, &nodes[0]));
+        assert_eq!(vec![15, 30], elems(&list));
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_enumerate_elems() {
+        let nodes = setup_from(&[5, 6, 7]);
+        let list = new_list(&nodes);
+
+        assert_eq!(vec![(0, &5), (1, &6), (2, &7)], list.enumerate_elems());
+        assert!(new_list(&nodes[..0]).enumerate_elems().is_empty());
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_is_palindrome() {
+        let is_palindrome = |elems: &[usize]| new_list(&setup_from(elems)).is_palindrome();
+
+        assert!(is_palindrome(&[]));
+        assert!(is_palindrome(&[1]));
+        assert!(is_palindrome(&[1, 2, 2, 1]));
+        assert!(is_palindrome(&[1, 2, 3, 2, 1]));
+
+        assert!(!is_palindrome(&[1, 2]));
+        assert!(!is_palindrome(&[1, 2, 3, 1]));
+        assert!(!is_palindrome(&[1, 2, 3, 1, 1]));
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_transfer_front_n() {
+        let nodes = setup_from(&[1, 2, 3, 4, 5, 6]);
+
+        let mut list = new_list(&nodes[..5]);
+        let dest = new_list(&nodes[5..]);
+
+        list.transfer_front_n(2, &dest);
+        assert_eq!(vec![3, 4, 5], elems(&list));
+        assert_eq!(vec![6, 1, 2], elems(&dest));
+        assert_eq!(vec![2, 1, 6], rev_elems(&dest));
+
+        list.transfer_front_n(0, &dest);
+        assert_eq!(vec![3, 4, 5], elems(&list));
+
+        list.transfer_front_n(10, &dest);
+        assert!(list.is_empty());
+        assert_eq!(vec![6, 1, 2, 3, 4, 5], elems(&dest));
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_round_robin_iter() {
+        let nodes0 = setup_from(&[1, 3, 5]);
+        let nodes1 = setup_from(&[2, 4]);
+
+        let list0 = new_list(&nodes0);
+        let list1 = new_list(&nodes1);
+
+        let round_robin: Vec<_> = list0
+            .round_robin_iter(&list1)
+            .map(|node| *node.get_elem())
+            .collect();
+        assert_eq!(vec![1, 2, 3, 4, 5], round_robin);
+
+        let round_robin: Vec<_> = list1
+            .round_robin_iter(&list0)
+            .map(|node| *node.get_elem())
+            .collect();
+        assert_eq!(vec![2, 1, 4, 3, 5], round_robin);
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_stats() {
+        let nodes = setup_from(&[3, 1, 4, 1, 5]);
+
+        assert_eq!(
+            ListStats { count: 5, min: Some(&1), max: Some(&5) },
+            new_list(&nodes).stats()
+        );
+        assert_eq!(
+            ListStats { count: 0, min: None, max: None },
+            new_list(&nodes[..0]).stats()
+        );
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_compare_exchange_front() {
+        let nodes = setup_from(&[1, 2, 3, 4]);
+        let mut list = new_list(&nodes[..3]);
+
+        assert_matches!(unsafe { list.compare_exchange_front(&nodes[0], &nodes[3]) }, Ok(()));
+        assert_eq!(vec![4, 2, 3], elems(&list));
+        assert_eq!(vec![3, 2, 4], rev_elems(&list));
+
+        assert_matches!(
+            unsafe { list.compare_exchange_front(&nodes[1], &nodes[0]) },
+            Err(Some(node)) if ptr::eq(node, &nodes[3])
+        );
+        assert_eq!(vec![4, 2, 3], elems(&list));
+
+        let mut list = new_list(&nodes[..0]);
+        assert_matches!(unsafe { list.compare_exchange_front(&nodes[1], &nodes[0]) }, Err(None));
+        assert!(list.is_empty());
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_iter_permuted() {
+        let nodes = setup_from(&[0, 1, 2]);
+        let list = new_list(&nodes);
+
+        let permuted: Vec<_> = list.iter_permuted(&[2, 0, 1]).collect();
+        assert_eq!(3, permuted.len());
+        assert!(ptr::eq(permuted[0], &nodes[2]));
+        assert!(ptr::eq(permuted[1], &nodes[0]));
+        assert!(ptr::eq(permuted[2], &nodes[1]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_list_iter_permuted_out_of_range() {
+        let nodes = setup_from(&[0, 1, 2]);
+        new_list(&nodes).iter_permuted(&[3]).count();
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_elem_after() {
+        let nodes = setup_from(&[1, 2, 3]);
+        let list = new_list(&nodes);
+
+        assert_eq!(Some(&3), list.elem_after(|node| *node.get_elem() == 2));
+        assert_eq!(None, list.elem_after(|node| *node.get_elem() == 3));
+        assert_eq!(None, list.elem_after(|node| *node.get_elem() == 4));
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_extract_every_nth() {
+        let nodes = setup_from(&[1, 2, 3, 4, 5]);
+        let mut list = new_list(&nodes);
+
+        let extracted = list.extract_every_nth(2);
+        assert_eq!(vec![1, 3, 5], elems(&extracted));
+        assert_eq!(vec![5, 3, 1], rev_elems(&extracted));
+        assert_eq!(vec![2, 4], elems(&list));
+        assert_eq!(vec![4, 2], rev_elems(&list));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_list_extract_every_nth_zero_step() {
+        let nodes = setup_from(&[1, 2, 3]);
+        new_list(&nodes).extract_every_nth(0);
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_update_each() {
+        let nodes: Vec<Node<UnsafeCell<usize>>> = (1..5)
+            .map(|elem| Node::new(UnsafeCell::new(elem)))
+            .collect();
+
+        let mut list = IntrusiveList::new();
+        for node in &nodes {
+            unsafe { list.push_back(node) };
+        }
+
+        unsafe { list.update_each(|elem| *elem *= 2) };
+
+        let elems: Vec<_> = list.iter().map(|node| unsafe { *node.get_elem_mut() }).collect();
+        assert_eq!(vec![2, 4, 6, 8], elems);
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_clear_matching() {
+        let nodes = setup_from(&[5, 1, 7, 3, 9, 2]);
+        let mut list = new_list(&nodes);
+
+        assert_eq!(3, list.clear_matching(|node| *node.get_elem() > 4));
+        assert_eq!(vec![1, 3, 2], elems(&list));
+        assert_eq!(vec![2, 3, 1], rev_elems(&list));
+
+        assert_eq!(0, list.clear_matching(|node| *node.get_elem() > 4));
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_link_report() {
+        let nodes = setup_from(&[1, 2, 3, 4]);
+        let list = new_list(&nodes);
+
+        assert_eq!(
+            LinkReport { forward_len: 4, backward_len: 4, consistent: true },
+            list.link_report()
+        );
+
+        nodes[2].get_prev_ptr().store(ptr::null_mut(), Ordering::Relaxed);
+        assert_eq!(
+            LinkReport { forward_len: 4, backward_len: 2, consistent: false },
+            list.link_report()
+        );
+
+        assert_eq!(
+            LinkReport { forward_len: 0, backward_len: 0, consistent: true },
+            new_list(&nodes[..0]).link_report()
+        );
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_link_report_cyclic() {
+        let nodes = setup_from(&[1, 2, 3]);
+        let mut list = new_list(&nodes);
+
+        #[cfg(feature = "counter")]
+        let max_len = 3;
+        #[cfg(not(feature = "counter"))]
+        let max_len = DEBUG_MAX_ELEMS;
+
+        unsafe { list.make_cyclic() };
+        assert_eq!(
+            LinkReport { forward_len: max_len, backward_len: max_len, consistent: false },
+            list.link_report()
+        );
+
+        list.break_cyclic();
+        assert_eq!(
+            LinkReport { forward_len: 3, backward_len: 3, consistent: true },
+            list.link_report()
+        );
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_push_empty_splice() {
+        let nodes = setup_from(&[1, 2]);
+
+        let list: IntrusiveList<Node> = IntrusiveList::new();
+        list.push_back_splice(Splice::new_empty());
+        list.push_front_splice(Splice::new_empty());
+        assert!(list.is_empty());
+
+        let list = new_list(&nodes);
+        list.push_back_splice(Splice::new_empty());
+        list.push_front_splice(Splice::new_empty());
+        assert_eq!(vec![1, 2], elems(&list));
+        assert_eq!(vec![2, 1], rev_elems(&list));
+        assert!(list.link_report().consistent);
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_move_first_matching_to() {
+        let nodes = setup_from(&[1, 2, 3, 4, 5]);
+
+        let mut list = new_list(&nodes[..4]);
+        let dest = new_list(&nodes[4..]);
+
+        assert!(list.move_first_matching_to(|node| *node.get_elem() % 2 == 0, &dest));
+        assert_eq!(vec![1, 3, 4], elems(&list));
+        assert_eq!(vec![4, 3, 1], rev_elems(&list));
+        assert_eq!(vec![5, 2], elems(&dest));
+        assert_eq!(vec![2, 5], rev_elems(&dest));
+
+        assert!(!list.move_first_matching_to(|node| *node.get_elem() > 10, &dest));
+        assert_eq!(vec![1, 3, 4], elems(&list));
+        assert_eq!(vec![5, 2], elems(&dest));
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_iter_double_ended() {
+        let nodes = setup_from(&[1, 2, 3, 4, 5]);
+        let list = new_list(&nodes[..4]);
+
+        let mut iter = list.iter();
+
+        unsafe { list.push_back(&nodes[4]) };
+
+        assert_eq!(1, *iter.next().unwrap().get_elem());
+        assert_eq!(4, *iter.next_back().unwrap().get_elem());
+        assert_eq!(2, *iter.next().unwrap().get_elem());
+        assert_eq!(3, *iter.next_back().unwrap().get_elem());
+        assert!(iter.next().is_none());
+        assert!(iter.next_back().is_none());
+
+        assert_eq!(vec![1, 2, 3, 4, 5], elems(&list));
+        assert_eq!(vec![5, 4, 3, 2, 1], rev_elems(&list));
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_windows_elems() {
+        let nodes = setup_from(&[1, 2, 3]);
+        let list = new_list(&nodes);
+
+        assert_eq!(
+            vec![vec![1, 2], vec![2, 3]],
+            list.windows_elems(2)
+                .map(|window| window.into_iter().copied().collect::<Vec<_>>())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(3, list.windows_elems(1).count());
+        assert_eq!(0, list.windows_elems(4).count());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_list_windows_elems_zero() {
+        let nodes = setup_from(&[1, 2, 3]);
+        new_list(&nodes).windows_elems(0).count();
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_reset_from() {
+        let nodes = setup_from(&[1, 2, 3, 4, 5]);
+        let mut list = new_list(&nodes[..2]);
+
+        let old = unsafe { list.reset_from(&nodes[2..]) };
+        assert_eq!(vec![1, 2], old.iter().map(|node| *node.get_elem()).collect::<Vec<_>>());
+        assert_eq!(vec![3, 4, 5], elems(&list));
+        assert_eq!(vec![5, 4, 3], rev_elems(&list));
+
+        let old = unsafe { list.reset_from(old) };
+        assert_eq!(3, old.len());
+        assert_eq!(vec![1, 2], elems(&list));
+        assert_eq!(vec![2, 1], rev_elems(&list));
+    }
+
+    #[cfg(feature = "counter")]
+    #[concurrency_toolkit::test]
+    fn test_list_len() {
+        let nodes = setup_from(&[1, 2, 3, 4, 5, 6, 7, 8]);
+
+        let mut list = IntrusiveList::new();
+        assert_eq!(0, list.len());
+
+        for node in &nodes[..6] {
+            unsafe { list.push_back(node) };
+        }
+        assert_eq!(6, list.len());
+
+        let splice = unsafe { list.splice(&nodes[1], &nodes[2]) }.unwrap();
+        assert_eq!(4, list.len());
+
+        list.push_front_splice(splice);
+        assert_eq!(6, list.len());
+
+        assert!(unsafe { list.remove_node(&nodes[0]) });
+        assert_eq!(5, list.len());
+
+        assert_eq!((3, 2), list.remove_if(|node| *node.get_elem() > 4));
+        assert_eq!(3, list.len());
+
+        unsafe { list.push_front(&nodes[6]) };
+        unsafe { list.push_back(&nodes[7]) };
+        assert_eq!(5, list.len());
+        assert_eq!(list.iter().count(), list.len());
+
+        list.clear();
+        assert_eq!(0, list.len());
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_find_run() {
+        let nodes = setup_from(&[2, 1, 4, 6, 3, 8, 10, 12]);
+        let list = new_list(&nodes);
+        let is_even = |node: &Node| *node.get_elem() % 2 == 0;
+
+        let (first, last) = list.find_run(is_even, 2).unwrap();
+        assert!(ptr::eq(&nodes[2], first));
+        assert!(ptr::eq(&nodes[3], last));
+
+        let (first, last) = list.find_run(is_even, 3).unwrap();
+        assert!(ptr::eq(&nodes[5], first));
+        assert!(ptr::eq(&nodes[7], last));
+
+        assert!(list.find_run(is_even, 4).is_none());
+        assert!(list.find_run(|node| *node.get_elem() > 100, 1).is_none());
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_clear_then_reuse() {
+        let nodes = setup_from(&[1, 2, 3]);
+        let mut list = new_list(&nodes);
+
+        list.clear();
+        assert!(list.is_empty());
+        for node in &nodes {
+            assert!(node.get_next_ptr().load(Ordering::Relaxed).is_null());
+            assert!(node.get_prev_ptr().load(Ordering::Relaxed).is_null());
+        }
+
+        // Nodes cleared are not in `list`.
+        assert!(unsafe { !list.remove_node(&nodes[1]) });
+
+        for node in nodes.iter().rev() {
+            unsafe { list.push_back(node) };
+        }
+        assert_eq!(vec![3, 2, 1], elems(&list));
+        assert_eq!(vec![1, 2, 3], rev_elems(&list));
+        assert!(list.link_report().consistent);
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_pop_front_and_pop_back() {
+        let nodes = setup_from(&[1, 2, 3]);
+        let mut list = new_list(&nodes);
+
+        assert!(ptr::eq(&nodes[0], list.pop_front().unwrap()));
+        assert!(ptr::eq(&nodes[2], list.pop_back().unwrap()));
+        assert_eq!(vec![2], elems(&list));
+        assert_eq!(vec![2], rev_elems(&list));
+
+        for node in [&nodes[0], &nodes[2]] {
+            assert!(node.get_next_ptr().load(Ordering::Relaxed).is_null());
+            assert!(node.get_prev_ptr().load(Ordering::Relaxed).is_null());
+        }
+
+        assert!(ptr::eq(&nodes[1], list.pop_back().unwrap()));
+        assert!(list.is_empty());
+        assert!(list.first_ptr.load(Ordering::Relaxed).is_null());
+        assert!(list.last_ptr.load(Ordering::Relaxed).is_null());
+
+        assert!(list.pop_front().is_none());
+        assert!(list.pop_back().is_none());
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_pop_front_while() {
+        let nodes = setup_from(&[2, 4, 5, 6]);
+        let mut list = new_list(&nodes);
+
+        let popped = list.pop_front_while(|node| *node.get_elem() % 2 == 0);
+        assert_eq!(vec![2, 4], popped.iter().map(|node| *node.get_elem()).collect::<Vec<_>>());
+        assert_eq!(vec![5, 6], elems(&list));
+        assert_eq!(vec![6, 5], rev_elems(&list));
+
+        assert!(list.pop_front_while(|node| *node.get_elem() % 2 == 0).is_empty());
+
+        assert_eq!(2, list.pop_front_while(|_| true).len());
+        assert!(list.is_empty());
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_histogram() {
+        let nodes = setup_from(&[1, 1, 2, 3, 3, 3]);
+        let list = new_list(&nodes);
+
+        let histogram = list.histogram();
+        assert_eq!(3, histogram.len());
+        assert_eq!(2, histogram[&1]);
+        assert_eq!(1, histogram[&2]);
+        assert_eq!(3, histogram[&3]);
+
+        assert!(new_list(&nodes[..0]).histogram().is_empty());
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_contains() {
+        let nodes = setup_from(&[1, 2, 3, 4]);
+
+        let list = new_list(&nodes[..2]);
+        let other = new_list(&nodes[2..3]);
+
+        assert!(list.contains(&nodes[0]));
+        assert!(list.contains(&nodes[1]));
+
+        // node linked in another list
+        assert!(!list.contains(&nodes[2]));
+        assert!(other.contains(&nodes[2]));
+
+        // node never pushed
+        assert!(!list.contains(&nodes[3]));
+        assert!(!other.contains(&nodes[3]));
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_swap_ends() {
+        let nodes = setup_from(&[1, 2, 3, 4, 5]);
+
+        let mut list = new_list(&nodes);
+        assert!(list.swap_ends());
+        assert_eq!(vec![5, 2, 3, 4, 1], elems(&list));
+        assert_eq!(vec![1, 4, 3, 2, 5], rev_elems(&list));
+
+        let nodes = setup_from(&[1, 2]);
+        let mut list = new_list(&nodes);
+        assert!(list.swap_ends());
+        assert_eq!(vec![2, 1], elems(&list));
+        assert_eq!(vec![1, 2], rev_elems(&list));
+
+        let nodes = setup_from(&[1]);
+        let mut list = new_list(&nodes);
+        assert!(!list.swap_ends());
+        assert_eq!(vec![1], elems(&list));
+
+        assert!(!new_list(&nodes[..0]).swap_ends());
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_iter_with_rindex() {
+        let nodes = setup_from(&[1, 2, 3]);
+        let list = new_list(&nodes);
+
+        assert_eq!(
+            vec![(2, 1), (1, 2), (0, 3)],
+            list.iter_with_rindex()
+                .map(|(rindex, node)| (rindex, *node.get_elem()))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(0, new_list(&nodes[..0]).iter_with_rindex().count());
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_debug() {
+        let nodes = setup_from(&[1, 2, 3]);
+
+        let mut list = new_list(&nodes);
+        assert_eq!("[1, 2, 3]", format!("{:?}", list));
+
+        let splice = unsafe { list.splice(&nodes[1], &nodes[2]) }.unwrap();
+        assert_eq!("[2, 3]", format!("{:?}", splice));
+        assert_eq!("[1]", format!("{:?}", list));
+
+        assert_eq!("[]", format!("{:?}", Splice::<Node>::new_empty()));
+
+        let nodes: Vec<Node> = (0..DEBUG_MAX_ELEMS + 1).map(Node::new).collect();
+        let list = new_list(&nodes);
+        assert!(format!("{:?}", list).ends_with(&format!("{}, ..]", DEBUG_MAX_ELEMS - 1)));
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_append() {
+        let nodes = setup_from(&[1, 2, 3, 4, 5]);
+
+        let list = new_list(&nodes[..2]);
+        let mut other = new_list(&nodes[2..]);
+
+        list.append(&mut other);
+        assert_eq!(vec![1, 2, 3, 4, 5], elems(&list));
+        assert_eq!(vec![5, 4, 3, 2, 1], rev_elems(&list));
+        assert!(other.is_empty());
+        #[cfg(feature = "counter")]
+        assert_eq!((5, 0), (list.len(), other.len()));
+
+        list.append(&mut other);
+        assert_eq!(vec![1, 2, 3, 4, 5], elems(&list));
+
+        let nodes = setup_from(&[1, 2]);
+        let list = IntrusiveList::new();
+        let mut other = new_list(&nodes);
+        list.append(&mut other);
+        assert_eq!(vec![1, 2], elems(&list));
+        assert_eq!(vec![2, 1], rev_elems(&list));
+        assert!(other.is_empty());
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_audit_ownership() {
+        let nodes: Vec<_> = (0..4)
+            .map(|elem| TaggedNodeImpl {
+                node: Node::new(elem),
+                tag: AtomicPtr::new(ptr::null_mut()),
+            })
+            .collect();
+
+        let list = IntrusiveList::new();
+        let other: IntrusiveList<TaggedNodeImpl> = IntrusiveList::new();
+        assert!(list.audit_ownership());
+
+        let tag = &list as *const _ as *mut ();
+        for node in &nodes[..3] {
+            node.tag.store(tag, Ordering::Relaxed);
+            unsafe { list.push_back(node) };
         }
+        assert!(list.audit_ownership());
 
-        splice0.push_back_splice(splice1);
+        // `nodes[3]` claims to be in `other`, but is linked into `list`.
+        nodes[3].tag.store(&other as *const _ as *mut (), Ordering::Relaxed);
+        unsafe { list.push_back(&nodes[3]) };
+        assert!(!list.audit_ownership());
+    }
 
-        for (index, node) in splice0.iter().enumerate() {
-            assert_eq!(index, *node.get_elem());
-            assert_lt!(index, 100);
-        }
+    #[concurrency_toolkit::test]
+    fn test_list_split_off() {
+        let nodes = setup_from(&[1, 2, 3, 4, 5]);
+
+        let mut list = new_list(&nodes);
+        let tail = list.split_off(&nodes[2]);
+        assert_eq!(vec![1, 2], elems(&list));
+        assert_eq!(vec![2, 1], rev_elems(&list));
+        assert_eq!(vec![3, 4, 5], elems(&tail));
+        assert_eq!(vec![5, 4, 3], rev_elems(&tail));
+
+        let nodes = setup_from(&[1, 2, 3, 4, 5]);
+        let mut list = new_list(&nodes);
+        let tail = list.split_off(&nodes[4]);
+        assert_eq!(vec![1, 2, 3, 4], elems(&list));
+        assert_eq!(vec![4, 3, 2, 1], rev_elems(&list));
+        assert_eq!(vec![5], elems(&tail));
+
+        let nodes = setup_from(&[1, 2, 3, 4, 5]);
+        let mut list = new_list(&nodes);
+        let tail = list.split_off(&nodes[0]);
+        assert!(list.is_empty());
+        assert_eq!(vec![1, 2, 3, 4, 5], elems(&tail));
+        assert_eq!(vec![5, 4, 3, 2, 1], rev_elems(&tail));
+
+        let other = setup_from(&[6]);
+        let nodes = setup_from(&[1, 2, 3, 4, 5]);
+        let mut list = new_list(&nodes);
+        assert!(list.split_off(&other[0]).is_empty());
+        assert_eq!(vec![1, 2, 3, 4, 5], elems(&list));
     }
 
     #[concurrency_toolkit::test]
-    fn test_splice_push_back_and_push_front() {
-        let nodes = setup();
+    fn test_list_rfold() {
+        let nodes = setup_from(&[1, 2, 3]);
+        let list = new_list(&nodes);
 
-        let mut splice: Splice<'_, _> = Default::default();
+        let s = list.rfold(String::new(), |mut s, node| {
+            s.push_str(&node.get_elem().to_string());
+            s
+        });
+        assert_eq!("321", s);
+        assert_eq!(0, new_list(&nodes[..0]).rfold(0, |acc, node| acc + node.get_elem()));
+    }
 
-        // Test push_back + push_front + next
-        for node in &nodes {
-            if *node.get_elem() % 2 == 0 {
-                unsafe { splice.push_back(node) };
-            } else {
-                unsafe { splice.push_front(node) };
-            }
+    #[concurrency_toolkit::test]
+    fn test_list_insert_after_and_insert_before() {
+        let nodes = setup_from(&[2, 4, 1, 3, 5, 0, 6]);
+        let list = new_list(&nodes[..2]);
+
+        unsafe {
+            // in the middle
+            list.insert_after(&nodes[0], &nodes[3]);
+            // at the head
+            list.insert_before(&nodes[0], &nodes[2]);
+            // at the tail
+            list.insert_after(&nodes[1], &nodes[4]);
+        }
+        assert_eq!(vec![1, 2, 3, 4, 5], elems(&list));
+        assert_eq!(vec![5, 4, 3, 2, 1], rev_elems(&list));
 
-            assert!(!splice.is_empty());
+        unsafe {
+            list.insert_before(&nodes[2], &nodes[5]);
+            list.push_back(&nodes[6]);
         }
+        assert_eq!(vec![0, 1, 2, 3, 4, 5, 6], elems(&list));
+        assert_eq!(vec![6, 5, 4, 3, 2, 1, 0], rev_elems(&list));
+        assert!(list.link_report().consistent);
+    }
 
-        let mut iter = splice.iter();
+    #[concurrency_toolkit::test]
+    fn test_list_split_at_first() {
+        let nodes = setup_from(&[1, 2, 3, 4]);
+
+        let mut list = new_list(&nodes);
+        let tail = list.split_at_first(|node| *node.get_elem() > 2).unwrap();
+        assert_eq!(vec![1, 2], elems(&list));
+        assert_eq!(vec![3, 4], elems(&tail));
+        assert_eq!(vec![4, 3], rev_elems(&tail));
+
+        let nodes = setup_from(&[1, 2, 3, 4]);
+        let mut list = new_list(&nodes);
+        let tail = list.split_at_first(|node| *node.get_elem() == 1).unwrap();
+        assert!(list.is_empty());
+        assert_eq!(vec![1, 2, 3, 4], elems(&tail));
 
-        for (index, node) in (1..100).rev().step_by(2).zip(&mut iter) {
-            assert_lt!(index, 100);
-            assert_eq!(index % 2, 1);
-            assert_eq!(index, *node.get_elem());
+        let nodes = setup_from(&[1, 2, 3, 4]);
+        let mut list = new_list(&nodes);
+        assert!(list.split_at_first(|node| *node.get_elem() > 4).is_none());
+        assert_eq!(vec![1, 2, 3, 4], elems(&list));
+    }
+
+    /// Pushes nodes from two threads while reading `list` on the main thread.
+    ///
+    /// With feature `permutation_testing`, this is checked exhaustively by
+    /// loom, otherwise it is repeated to exercise as many interleavings as
+    /// possible, including the retry of `push_back` when the last node changed
+    /// after being loaded.
+    ///
+    /// Since `push_back` waits for the other thread to finish its push,
+    /// loom needs `LOOM_MAX_PREEMPTIONS=2` to finish, which is set by
+    /// `run_tests.sh`.
+    #[concurrency_toolkit::test]
+    fn test_list_push_back_stress() {
+        #[cfg(feature = "permutation_testing")]
+        const ROUNDS: usize = 1;
+        #[cfg(not(feature = "permutation_testing"))]
+        const ROUNDS: usize = 1000;
+
+        fn is_ordered(elems: &[usize], filter: impl Fn(usize) -> bool) -> bool {
+            elems.iter()
+                .copied()
+                .filter(|elem| filter(*elem))
+                .collect::<Vec<_>>()
+                .windows(2)
+                .all(|window| window[0] < window[1])
         }
-        assert!(!splice.is_empty());
 
-        for (index, node) in (0..100).step_by(2).zip(iter) {
-            assert_lt!(index, 100);
-            assert_eq!(index % 2, 0);
-            assert_eq!(index, *node.get_elem());
+        for _ in 0..ROUNDS {
+            // `IntrusiveList` does not access its nodes on drop, so it is fine
+            // for `list` to claim that `nodes` lives for `'static`, as long as
+            // `list` is not used after the last clone of `nodes` is dropped.
+            let nodes = Arc::new(setup_from(&[0, 1, 2, 3]));
+            let list: Arc<IntrusiveList<'static, Node>> = Arc::new(IntrusiveList::new());
+
+            let handle0 = {
+                let (nodes, list) = (nodes.clone(), list.clone());
+                spawn!({
+                    for node in &nodes[..2] {
+                        unsafe { list.push_back(&*(node as *const Node)) };
+                    }
+                })
+            };
+            let handle1 = {
+                let (nodes, list) = (nodes.clone(), list.clone());
+                spawn!({
+                    for node in &nodes[2..] {
+                        unsafe { list.push_back(&*(node as *const Node)) };
+                    }
+                })
+            };
+
+            let seen = elems(&list);
+            assert!(seen.len() <= 4);
+            assert!(is_ordered(&seen, |elem| elem < 2));
+            assert!(is_ordered(&seen, |elem| elem >= 2));
+
+            join!(handle0).unwrap();
+            join!(handle1).unwrap();
+
+            let mut seen = elems(&list);
+            assert!(is_ordered(&seen, |elem| elem < 2));
+            assert!(is_ordered(&seen, |elem| elem >= 2));
+
+            seen.sort_unstable();
+            assert_eq!(vec![0, 1, 2, 3], seen);
+
+            assert_eq!(
+                LinkReport { forward_len: 4, backward_len: 4, consistent: true },
+                list.link_report()
+            );
         }
     }
 
     #[concurrency_toolkit::test]
-    fn test_splice_push_back_splice_and_push_front_splice() {
-        let nodes = setup();
+    fn test_list_iter_peekable_elems() {
+        let nodes = setup_from(&[1, 2]);
+        let list = new_list(&nodes);
+
+        let mut iter = list.iter_peekable_elems();
+        assert_eq!(Some(&1), iter.peek());
+        assert_eq!(Some(&1), iter.peek());
+        assert_eq!(Some(&1), iter.next());
+        assert_eq!(Some(&2), iter.peek());
+        assert_eq!(Some(&2), iter.next());
+        assert_eq!(None, iter.peek());
+        assert_eq!(None, iter.next());
+    }
 
-        let mut splice0: Splice<'_, _> = Default::default();
-        let mut splice1: Splice<'_, _> = Default::default();
+    #[concurrency_toolkit::test]
+    fn test_list_front_and_back() {
+        let nodes = setup_from(&[1, 2, 3]);
 
-        for node in &nodes {
-            if *node.get_elem() % 2 == 0 {
-                unsafe { splice0.push_back(node) };
-                assert!(!splice0.is_empty());
-            } else {
-                unsafe { splice1.push_front(node) };
-                assert!(!splice1.is_empty());
-            }
-        }
+        let list = new_list(&nodes[..0]);
+        assert!(list.front().is_none());
+        assert!(list.back().is_none());
 
-        let mut splice:  Splice<'_, _> = Default::default();
-        splice.push_back_splice(splice0);
-        splice.push_front_splice(splice1);
+        let list = new_list(&nodes[..1]);
+        assert!(ptr::eq(&nodes[0], list.front().unwrap()));
+        assert!(ptr::eq(&nodes[0], list.back().unwrap()));
 
-        let mut iter = splice.iter();
+        let list = new_list(&nodes);
+        assert!(ptr::eq(&nodes[0], list.front().unwrap()));
+        assert!(ptr::eq(&nodes[2], list.back().unwrap()));
+    }
 
-        for (index, node) in (1..100).rev().step_by(2).zip(&mut iter) {
-            assert_lt!(index, 100);
-            assert_eq!(index % 2, 1);
-            assert_eq!(index, *node.get_elem());
-        }
-        assert!(!splice.is_empty());
+    #[concurrency_toolkit::test]
+    fn test_list_make_cyclic_and_break_cyclic() {
+        let nodes = setup_from(&[1, 2, 3]);
+        let mut list = new_list(&nodes);
+
+        unsafe { list.make_cyclic() };
+        assert!(ptr::eq(nodes[2].get_next_ptr().load(Ordering::Relaxed), &nodes[0] as *const _ as *mut ()));
+        assert!(ptr::eq(nodes[0].get_prev_ptr().load(Ordering::Relaxed), &nodes[2] as *const _ as *mut ()));
+        assert_eq!(vec![1, 2, 3], elems(&list));
+
+        list.break_cyclic();
+        assert_eq!(vec![1, 2, 3], elems(&list));
+        assert_eq!(vec![3, 2, 1], rev_elems(&list));
+        assert!(list.link_report().consistent);
+
+        let mut list = new_list(&nodes[..0]);
+        unsafe { list.make_cyclic() };
+        list.break_cyclic();
+        assert!(list.is_empty());
+    }
 
-        for (index, node) in (0..100).step_by(2).zip(iter) {
-            assert_lt!(index, 100);
-            assert_eq!(index % 2, 0);
-            assert_eq!(index, *node.get_elem());
-        }
+    #[concurrency_toolkit::test]
+    fn test_list_count_prefix() {
+        let nodes = setup_from(&[2, 4, 5, 6]);
+        let list = new_list(&nodes);
+
+        assert_eq!(2, list.count_prefix(|node| *node.get_elem() % 2 == 0));
+        assert_eq!(0, list.count_prefix(|node| *node.get_elem() > 2));
+        assert_eq!(4, list.count_prefix(|_| true));
     }
 
     #[concurrency_toolkit::test]
-    fn test_iterator() {
-        let nodes = setup();
+    fn test_list_remove_all() {
+        let nodes = setup_from(&[1, 2, 3, 4, 5, 6]);
+        let mut list = new_list(&nodes[..5]);
+
+        // contiguous run out of order, duplicates and node not in `list`
+        let removed = list.remove_all(&[&nodes[3], &nodes[1], &nodes[2], &nodes[1], &nodes[5]]);
+        assert_eq!(3, removed);
+        assert_eq!(vec![1, 5], elems(&list));
+        assert_eq!(vec![5, 1], rev_elems(&list));
+
+        assert_eq!(0, list.remove_all(&[]));
+        assert_eq!(2, list.remove_all(&[&nodes[4], &nodes[0]]));
+        assert!(list.is_empty());
+    }
 
-        let mut splice: Splice<'_, _> = Default::default();
- 
-        assert_matches!(splice.iter().next(), None);
-        assert_matches!(splice.iter().last(), None);
-        assert_matches!(splice.iter().next_back(), None);
+    #[concurrency_toolkit::test]
+    fn test_list_merge_splice_sorted() {
+        let nodes = setup_from(&[1, 3, 5, 2, 4, 6, 0]);
+        let less = |x: &Node, y: &Node| x.get_elem() < y.get_elem();
 
-        for node in &nodes {
+        let mut list = new_list(&nodes[..3]);
+        let mut splice = Splice::new_empty();
+        for node in &nodes[3..5] {
             unsafe { splice.push_back(node) };
-            assert!(!splice.is_empty());
         }
 
-        assert_matches!(splice.iter().next(), Some(node) if *node.get_elem() == 0);
-        assert_matches!(splice.iter().last(), Some(node) if *node.get_elem() == 99);
-        assert_matches!(splice.iter().next_back(), Some(node) if *node.get_elem() == 99);
+        list.merge_splice_sorted(splice, less);
+        assert_eq!(vec![1, 2, 3, 4, 5], elems(&list));
+        assert_eq!(vec![5, 4, 3, 2, 1], rev_elems(&list));
 
-        for (node, index) in splice.iter().rev().zip((0..100).rev()) {
-            assert_eq!(index, *node.get_elem());
+        let mut splice = Splice::new_empty();
+        for node in [&nodes[6], &nodes[5]] {
+            unsafe { splice.push_back(node) };
         }
+        list.merge_splice_sorted(splice, less);
+        assert_eq!(vec![0, 1, 2, 3, 4, 5, 6], elems(&list));
+        assert_eq!(vec![6, 5, 4, 3, 2, 1, 0], rev_elems(&list));
+
+        list.merge_splice_sorted(Splice::new_empty(), less);
+        assert_eq!(7, list.iter().count());
     }
 
     #[concurrency_toolkit::test]
-    fn test_list_push_back_splice() {
-        let nodes = setup();
+    fn test_list_from_nodes() {
+        let nodes = setup_from(&[1, 2, 3]);
 
-        let list = IntrusiveList::new();
-        eprintln!("list = {:#?}", list);
+        let list = unsafe { IntrusiveList::from_nodes(&nodes) };
+        assert_eq!(vec![1, 2, 3], elems(&list));
+        assert_eq!(vec![3, 2, 1], rev_elems(&list));
+        assert!(list.link_report().consistent);
 
-        for node in &nodes[0..50] {
-            unsafe { list.push_back(node) };
-            assert!(!list.is_empty());
-        }
+        let list = unsafe { IntrusiveList::from_nodes(&nodes[..0]) };
+        assert!(list.is_empty());
+    }
 
-        for (index, node) in list.iter().enumerate() {
-            assert_eq!(index, *node.get_elem());
-            assert_lt!(index, 50);
+    #[concurrency_toolkit::test]
+    fn test_list_iter_grouped_runs() {
+        let nodes = setup_from(&[1, 1, 2, 3, 3]);
+        let list = new_list(&nodes);
+
+        let runs: Vec<_> = list.iter_grouped_runs(|node| *node.get_elem()).collect();
+        assert_eq!(3, runs.len());
+
+        for ((key, first, last), (expected_key, expected_first, expected_last)) in
+            runs.into_iter().zip([(1, 0, 1), (2, 2, 2), (3, 3, 4)])
+        {
+            assert_eq!(expected_key, key);
+            assert!(ptr::eq(&nodes[expected_first], first));
+            assert!(ptr::eq(&nodes[expected_last], last));
         }
 
-        let mut splice: Splice<'_, _> = Default::default();
+        assert_eq!(0, new_list(&nodes[..0]).iter_grouped_runs(|node| *node.get_elem()).count());
+    }
 
-        for node in &nodes[50..100] {
-            unsafe { splice.push_back(node) };
-            assert!(!splice.is_empty());
-        }
+    #[concurrency_toolkit::test]
+    fn test_list_extend_back() {
+        let nodes = setup_from(&[1, 2, 3, 4, 5]);
 
-        for (index, node) in splice.iter().enumerate() {
-            assert_eq!(index + 50, *node.get_elem());
-            assert_lt!(index, 50);
-        }
+        let list = new_list(&nodes[..2]);
+        unsafe { list.extend_back(&nodes[2..]) };
+        assert_eq!(vec![1, 2, 3, 4, 5], elems(&list));
+        assert_eq!(vec![5, 4, 3, 2, 1], rev_elems(&list));
 
-        list.push_back_splice(splice);
+        unsafe { list.extend_back(&nodes[..0]) };
+        assert_eq!(5, list.iter().count());
 
-        for (index, node) in list.iter().enumerate() {
-            assert_eq!(index, *node.get_elem());
-            assert_lt!(index, 100);
-        }
+        let list = IntrusiveList::new();
+        unsafe { list.extend_back(&nodes) };
+        assert_eq!(vec![1, 2, 3, 4, 5], elems(&list));
+        assert!(list.link_report().consistent);
     }
 
     #[concurrency_toolkit::test]
-    fn test_list_push_back_splice_and_push_front_splice() {
-        let nodes = setup();
+    fn test_list_pop_back_while_into() {
+        let nodes = setup_from(&[1, 2, 3, 5]);
+        let mut list = new_list(&nodes);
+
+        let mut sink = Vec::new();
+        list.pop_back_while_into(|node| *node.get_elem() % 2 == 1, &mut sink);
+        assert_eq!(vec![&5, &3], sink);
+        assert_eq!(vec![1, 2], elems(&list));
+        assert_eq!(vec![2, 1], rev_elems(&list));
+
+        list.pop_back_while_into(|node| *node.get_elem() % 2 == 1, &mut sink);
+        assert_eq!(2, sink.len());
+    }
 
-        let mut splice0: Splice<'_, _> = Default::default();
-        let mut splice1: Splice<'_, _> = Default::default();
+    #[cfg(feature = "cache_padded")]
+    #[concurrency_toolkit::test]
+    fn test_list_cache_padded() {
+        let list: IntrusiveList<Node> = IntrusiveList::new();
 
-        for node in &nodes {
-            if *node.get_elem() % 2 == 0 {
-                unsafe { splice0.push_back(node) };
-                assert!(!splice0.is_empty());
-            } else {
-                unsafe { splice1.push_front(node) };
-                assert!(!splice1.is_empty());
-            }
+        let first = &list.first_ptr as *const _ as usize;
+        let last  = &list.last_ptr  as *const _ as usize;
+        assert!(first.abs_diff(last) >= 64);
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_is_rotation_of() {
+        let nodes = setup_from(&[1, 2, 3, 3, 1, 2, 2, 1, 3, 1, 2]);
+
+        let list = new_list(&nodes[..3]);
+        assert!(list.is_rotation_of(&new_list(&nodes[3..6])));
+        assert!(list.is_rotation_of(&list));
+        assert!(!list.is_rotation_of(&new_list(&nodes[6..9])));
+        assert!(!list.is_rotation_of(&new_list(&nodes[9..])));
+
+        let empty = new_list(&nodes[..0]);
+        assert!(empty.is_rotation_of(&new_list(&nodes[..0])));
+        assert!(!empty.is_rotation_of(&list));
+    }
+
+    #[test]
+    fn test_default_ordering() {
+        assert_eq!(Ordering::Acquire, DefaultOrdering::R_ORD);
+        assert_eq!(Ordering::Release, DefaultOrdering::W_ORD);
+        assert_eq!(Ordering::AcqRel, DefaultOrdering::RW_ORD);
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_relaxed_ordering() {
+        struct RelaxedOrdering;
+        unsafe impl OrderingPolicy for RelaxedOrdering {
+            const R_ORD: Ordering = Ordering::Relaxed;
+            const W_ORD: Ordering = Ordering::Relaxed;
+            const RW_ORD: Ordering = Ordering::Relaxed;
         }
 
-        let list = IntrusiveList::new();
-        list.push_back_splice(splice0);
-        list.push_front_splice(splice1);
-        assert!(!list.is_empty());
+        let nodes = setup_from(&[1, 2, 3, 4]);
 
-        let mut iter = list.iter();
+        let mut list: IntrusiveList<Node, RelaxedOrdering> = IntrusiveList::with_policy();
+        assert!(list.is_empty());
 
-        for (index, node) in (1..100).rev().step_by(2).zip(&mut iter) {
-            assert_lt!(index, 100);
-            assert_eq!(index % 2, 1);
-            assert_eq!(index, *node.get_elem());
+        unsafe {
+            list.push_back(&nodes[1]);
+            list.push_front(&nodes[0]);
+            list.push_back(&nodes[2]);
+            list.push_back(&nodes[3]);
         }
-        assert!(!list.is_empty());
+        assert_eq!(vec![&1, &2, &3, &4], list.iter_elems().collect::<Vec<_>>());
+        assert_eq!(vec![&4, &3, &2, &1], list.iter_elems().rev().collect::<Vec<_>>());
 
-        for (index, node) in (0..100).step_by(2).zip(iter) {
-            assert_lt!(index, 100);
-            assert_eq!(index % 2, 0);
-            assert_eq!(index, *node.get_elem());
+        assert_eq!((2, 2), list.remove_if(|node| *node.get_elem() % 2 == 0));
+        assert_eq!(vec![&1, &3], list.iter_elems().collect::<Vec<_>>());
+        assert!(list.link_report().consistent);
+
+        list.clear();
+        assert!(list.is_empty());
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_push_back_dedup() {
+        let nodes = setup_from(&[1, 2, 2, 1]);
+        let eq = |x: &Node, y: &Node| x.get_elem() == y.get_elem();
+
+        let mut list = new_list(&nodes[..0]);
+        unsafe {
+            assert!(list.push_back_dedup(&nodes[0], eq));
+            assert!(list.push_back_dedup(&nodes[1], eq));
+            assert!(!list.push_back_dedup(&nodes[2], eq));
+            assert!(list.push_back_dedup(&nodes[3], eq));
         }
+        assert_eq!(vec![1, 2, 1], elems(&list));
+        assert_eq!(vec![1, 2, 1], rev_elems(&list));
     }
 
     #[concurrency_toolkit::test]
-    fn test_list_clear() {
+    fn test_list_remove_if_elem() {
         let nodes = setup();
+        let mut list = new_list(&nodes);
 
-        let mut list = IntrusiveList::new();
+        assert_eq!((50, 50), list.remove_if_elem(|elem| *elem % 2 == 0));
+        assert_eq!((1..100).step_by(2).collect::<Vec<_>>(), elems(&list));
+        assert_eq!((1..100).step_by(2).rev().collect::<Vec<_>>(), rev_elems(&list));
 
-        for node in &nodes[0..50] {
-            unsafe { list.push_back(node) };
+        assert_eq!((0, 50), list.remove_if_elem(|_| true));
+        assert!(list.is_empty());
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_list_count_if() {
+        let nodes = setup();
+
+        let list = new_list(&nodes[..0]);
+        assert_eq!(0, list.count_if(|_| true));
+
+        let list = new_list(&nodes);
+        assert_eq!(100, list.count_if(|_| true));
+        assert_eq!(0, list.count_if(|_| false));
+        assert_eq!(34, list.count_if(|node| *node.get_elem() % 3 == 0));
+    }
+
+    /// Small enough to be run under Miri, to check the pointer casts.
+    #[concurrency_toolkit::test]
+    fn test_list_provenance() {
+        let nodes = setup_from(&[1, 2, 3, 4, 5]);
+
+        let mut list = IntrusiveList::new();
+        unsafe {
+            list.push_back(&nodes[2]);
+            list.push_front(&nodes[1]);
+            list.push_back(&nodes[3]);
+            list.push_front(&nodes[0]);
+            list.push_back(&nodes[4]);
         }
-        assert!(!list.is_empty());
+        assert_eq!(vec![1, 2, 3, 4, 5], elems(&list));
+        assert_eq!(vec![5, 4, 3, 2, 1], rev_elems(&list));
+
+        let splice = unsafe { list.splice(&nodes[1], &nodes[2]) }.unwrap();
+        assert_eq!(vec![2, 3], splice.iter().map(|node| node.elem).collect::<Vec<_>>());
+        assert_eq!(vec![1, 4, 5], elems(&list));
+
+        list.push_front_splice(splice);
+        assert_eq!(vec![2, 3, 1, 4, 5], elems(&list));
+
+        assert_eq!((3, 2), list.remove_if(|node| node.elem % 2 == 0));
+        assert_eq!(vec![3, 1, 5], elems(&list));
+        assert_eq!(vec![5, 1, 3], rev_elems(&list));
 
         list.clear();
         assert!(list.is_empty());
-
-        assert_eq!("[]", format!("{:#?}", list));
     }
 
+    #[cfg(feature = "serde")]
     #[concurrency_toolkit::test]
-    fn test_list_splice() {
-        let nodes = setup();
+    fn test_list_serialize() {
+        let nodes = setup_from(&[3, 1, 2]);
 
-        let mut list = IntrusiveList::new();
+        let list = new_list(&nodes[..0]);
+        assert_eq!("[]", serde_json::to_string(&list).unwrap());
 
-        for node in &nodes {
-            unsafe { list.push_back(node) };
-        }
-        assert!(!list.is_empty());
+        let list = new_list(&nodes);
+        assert_eq!("[3,1,2]", serde_json::to_string(&list).unwrap());
+    }
 
-        let first = list.iter().nth(50).unwrap();
-        let last  = list.iter().last().unwrap();
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "pushed twice")]
+    fn test_list_push_back_twice() {
+        let nodes = setup_from(&[1, 2]);
 
-        // TODO: Failed in `unwrap()` when testing under miri
-        for (index, node) in unsafe {
-            list.splice(first, last).unwrap()
-        }.iter().enumerate() {
-            assert_eq!(index + 50, *node.get_elem());
-            assert_lt!(index, 50);
-        }
+        let list = new_list(&nodes);
+        unsafe { list.push_back(&nodes[0]) };
+    }
 
-        for (index, node) in list.iter().enumerate() {
-            assert_eq!(index, *node.get_elem());
-            assert_lt!(index, 50);
-        }
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "pushed twice")]
+    fn test_list_push_front_twice() {
+        let nodes = setup_from(&[1]);
+
+        let list = new_list(&nodes);
+        unsafe { list.push_front(&nodes[0]) };
     }
 
     #[concurrency_toolkit::test]
-    fn test_list_remove_if() {
-        let nodes = setup();
+    fn test_list_drain() {
+        let nodes = setup_from(&[1, 2, 3, 4, 5]);
 
-        let mut list = IntrusiveList::new();
+        let mut list = new_list(&nodes[..0]);
+        assert_eq!(0, list.drain().count());
 
-        for node in &nodes {
-            unsafe { list.push_back(node) };
+        let mut list = new_list(&nodes);
+        let drained: Vec<_> = list.drain().collect();
+        assert!(list.is_empty());
+        assert_eq!(5, drained.len());
+
+        let mut other = IntrusiveList::new();
+        for node in drained {
+            unsafe { other.push_front(node) };
         }
-        assert!(!list.is_empty());
+        assert_eq!(vec![5, 4, 3, 2, 1], elems(&other));
+        assert_eq!(vec![1, 2, 3, 4, 5], rev_elems(&other));
 
-        assert_eq!((50, 50), list.remove_if(|node| *node.get_elem() % 2 == 1));
+        let mut drain = other.drain();
+        assert_eq!(Some(5), drain.next().map(|node| node.elem));
+        assert_eq!(Some(1), drain.next_back().map(|node| node.elem));
+        assert_eq!(Some(2), drain.next_back().map(|node| node.elem));
+        drop(drain);
+        assert!(other.is_empty());
 
-        for (index, node) in (0..100).step_by(2).zip(&list) {
-            assert_eq!(index, *node.get_elem());
+        // Nodes left in `drain` when dropped are also reset
+        for node in &nodes {
+            unsafe { list.push_back(node) };
         }
+        assert_eq!(vec![1, 2, 3, 4, 5], elems(&list));
+        assert_eq!(vec![5, 4, 3, 2, 1], rev_elems(&list));
     }
 
     #[concurrency_toolkit::test]
-    fn test_list_push_back_splice_concurrent() {
-        static NODES: Lazy<Vec<Node>> = Lazy::new(setup);
-        static LIST: Lazy<IntrusiveList<'static, Node>> = Lazy::new(IntrusiveList::new);
+    fn test_list_swap_nodes() {
+        // Covers adjacent nodes in both orders, the same node and the ends
+        for i in 0..4 {
+            for j in 0..4 {
+                let nodes = setup_from(&[1, 2, 3, 4]);
+                let mut list = new_list(&nodes);
+                assert!(list.swap_nodes(&nodes[i], &nodes[j]));
+
+                let mut expected = vec![1, 2, 3, 4];
+                expected.swap(i, j);
+                assert_eq!(expected, elems(&list));
+                expected.reverse();
+                assert_eq!(expected, rev_elems(&list));
+            }
+        }
 
-        let mut splice0: Splice<'_, _> = Default::default();
-        let mut splice1: Splice<'_, _> = Default::default();
+        let nodes = setup_from(&[1, 2]);
+        let mut list = new_list(&nodes);
+        assert!(list.swap_nodes(&nodes[1], &nodes[0]));
+        assert_eq!(vec![2, 1], elems(&list));
+        assert_eq!(vec![1, 2], rev_elems(&list));
+
+        let nodes = setup_from(&[1]);
+        let mut list = new_list(&nodes);
+        assert!(list.swap_nodes(&nodes[0], &nodes[0]));
+        assert_eq!(vec![1], elems(&list));
+
+        let nodes = setup_from(&[1, 2, 3, 4]);
+        let other = setup_from(&[5]);
+        let mut list = new_list(&nodes);
+        assert!(!list.swap_nodes(&nodes[0], &other[0]));
+        assert!(!list.swap_nodes(&other[0], &nodes[3]));
+        assert_eq!(vec![1, 2, 3, 4], elems(&list));
+        assert_eq!(vec![4, 3, 2, 1], rev_elems(&list));
+    }
 
-        let nodes = &*NODES;
+    #[concurrency_toolkit::test]
+    fn test_list_find() {
+        let nodes = setup_from(&[1, 2, 3, 2]);
 
-        for node in &nodes[0..50] {
-            unsafe { splice0.push_back(node) };
-        }
+        let list = new_list(&nodes[..0]);
+        assert!(list.find(|_| true).is_none());
 
-        for node in &nodes[50..100] {
-            unsafe { splice1.push_back(node) };
-        }
+        let list = new_list(&nodes);
+        assert!(ptr::eq(&nodes[2], list.find(|node| node.elem == 3).unwrap()));
+        assert!(list.find(|node| node.elem == 4).is_none());
 
-        let handle0 = spawn!({
-            LIST.push_back_splice(splice0);
+        let mut visited = 0;
+        let found = list.find(|node| {
+            visited += 1;
+            node.elem == 2
         });
+        assert!(ptr::eq(&nodes[1], found.unwrap()));
+        assert_eq!(2, visited);
+    }
 
-        let handle1 = spawn!({
-            LIST.push_back_splice(splice1);
-        });
+    #[concurrency_toolkit::test]
+    fn test_list_push_removed_nodes() {
+        let nodes = setup_from(&[1, 2, 3, 4]);
 
-        join!(handle0);
-        join!(handle1);
+        let mut list = new_list(&nodes);
+        assert!(unsafe { list.remove_node(&nodes[1]) });
+        unsafe { list.push_back(&nodes[1]) };
+        assert_eq!(vec![1, 3, 4, 2], elems(&list));
+
+        assert_eq!((1, 3), list.remove_if(|node| node.elem != 2));
+        for node in &nodes[2..] {
+            unsafe { list.push_front(node) };
+        }
+        assert_eq!(vec![4, 3, 2], elems(&list));
+        assert_eq!(vec![2, 3, 4], rev_elems(&list));
+
+        let splice = unsafe { list.splice(&nodes[3], &nodes[2]) }.unwrap();
+        list.push_back_splice(splice);
+        assert_eq!(vec![2, 4, 3], elems(&list));
+        assert_eq!(vec![3, 4, 2], rev_elems(&list));
     }
 }
@@ -1,5 +1,13 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "concurrency_toolkit")]
 pub extern crate concurrency_toolkit;
 
+// Tests require `std` and `concurrency_toolkit`, i.e. feature default or
+// permutation_testing, and are skipped otherwise.
 #[cfg(test)]
 extern crate assert_matches;
 #[cfg(test)]
@@ -10,3 +18,7 @@ extern crate once_cell;
 mod utility;
 pub mod intrusive_forward_list;
 pub mod intrusive_list;
+#[cfg(feature = "alloc")]
+pub mod owning_forward_list;
+#[cfg(feature = "alloc")]
+pub mod owned_intrusive_list;
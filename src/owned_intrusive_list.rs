@@ -0,0 +1,196 @@
+use core::marker::PhantomData;
+use core::fmt::{self, Debug, Formatter};
+
+use alloc::{boxed::Box, vec::Vec};
+
+use crate::intrusive_forward_list::IntrusiveForwardListNode;
+use crate::intrusive_list::*;
+use crate::utility::OwnedNode;
+
+type Node<T> = OwnedNode<IntrusiveListNodeImpl<T>>;
+
+/// `OwnedIntrusiveList` is a wrapper of `IntrusiveList` which allocates
+/// its nodes on heap, so that it can be used without `unsafe`.
+///
+/// Nodes are kept alive until they are removed or `self` is dropped.
+pub struct OwnedIntrusiveList<T: 'static> {
+    list: IntrusiveList<'static, Node<T>>,
+    phantom: PhantomData<Box<Node<T>>>,
+}
+impl<T: Debug> Debug for OwnedIntrusiveList<T> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_list().entries(self.iter_elems()).finish()
+    }
+}
+impl<T> Default for OwnedIntrusiveList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize> serde::Serialize for OwnedIntrusiveList<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter_elems())
+    }
+}
+/// Deserialize a sequence into a list, allocating a node for each element.
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for OwnedIntrusiveList<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Visitor<T>(PhantomData<T>);
+
+        impl<'de, T: serde::Deserialize<'de> + 'static> serde::de::Visitor<'de>
+            for Visitor<T>
+        {
+            type Value = OwnedIntrusiveList<T>;
+
+            fn expecting(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+                fmt.write_str("a sequence")
+            }
+
+            fn visit_seq<A: serde::de::SeqAccess<'de>>(self, mut seq: A)
+                -> Result<Self::Value, A::Error>
+            {
+                let list = OwnedIntrusiveList::new();
+                while let Some(elem) = seq.next_element()? {
+                    list.push_back(elem);
+                }
+                Ok(list)
+            }
+        }
+
+        deserializer.deserialize_seq(Visitor(PhantomData))
+    }
+}
+impl<T> OwnedIntrusiveList<T> {
+    pub fn new() -> Self {
+        Self {
+            list: IntrusiveList::new(),
+            phantom: PhantomData,
+        }
+    }
+
+    pub fn push_back(&self, elem: T) {
+        let node = Box::leak(Box::new(Node::new(IntrusiveListNodeImpl::new(elem))));
+        unsafe { self.list.push_back(node) };
+    }
+
+    pub fn push_front(&self, elem: T) {
+        let node = Box::leak(Box::new(Node::new(IntrusiveListNodeImpl::new(elem))));
+        unsafe { self.list.push_front(node) };
+    }
+
+    // All methods read the list:
+
+    pub fn iter_elems(&self) -> impl DoubleEndedIterator<Item = &T> + '_ {
+        self.list.iter().map(|node| -> &T { node.get_elem() })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    // All methods below are removal methods, which takes the write lock:
+
+    ///  * `f` - return true to remove the element or false to keep it
+    ///
+    /// Return (# num of elements left, # num of elements removed)
+    pub fn remove_if(&mut self, mut f: impl FnMut(&T) -> bool) -> (usize, usize) {
+        let mut removed = Vec::new();
+
+        let cnt = self.list.remove_if(|node| {
+            let remove = f(node.get_elem());
+            if remove {
+                removed.push(node as *const Node<T> as *mut Node<T>);
+            }
+            remove
+        });
+
+        for node in removed {
+            drop(unsafe { Box::from_raw(node) });
+        }
+
+        cnt
+    }
+
+    pub fn clear(&mut self) {
+        self.remove_if(|_| true);
+    }
+}
+impl<T> Drop for OwnedIntrusiveList<T> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+#[cfg(all(test, feature = "std", feature = "concurrency_toolkit"))]
+mod tests {
+    use super::*;
+
+    use std::rc::Rc;
+
+    #[concurrency_toolkit::test]
+    fn test_owned_list_push() {
+        let list = OwnedIntrusiveList::new();
+        assert!(list.is_empty());
+
+        for elem in 0..5 {
+            list.push_back(elem);
+            list.push_front(elem + 10);
+        }
+        assert!(!list.is_empty());
+
+        assert_eq!(
+            vec![14, 13, 12, 11, 10, 0, 1, 2, 3, 4],
+            list.iter_elems().copied().collect::<Vec<_>>()
+        );
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_owned_list_remove_if() {
+        let counter = Rc::new(());
+
+        let mut list = OwnedIntrusiveList::new();
+        for elem in 0..10 {
+            list.push_back((elem, counter.clone()));
+        }
+        assert_eq!(11, Rc::strong_count(&counter));
+
+        assert_eq!((5, 5), list.remove_if(|(elem, _)| elem % 2 == 0));
+        assert_eq!(6, Rc::strong_count(&counter));
+        assert_eq!(
+            vec![1, 3, 5, 7, 9],
+            list.iter_elems().map(|(elem, _)| *elem).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![9, 7, 5, 3, 1],
+            list.iter_elems().rev().map(|(elem, _)| *elem).collect::<Vec<_>>()
+        );
+
+        drop(list);
+        assert_eq!(1, Rc::strong_count(&counter));
+    }
+
+    #[cfg(feature = "serde")]
+    #[concurrency_toolkit::test]
+    fn test_owned_list_serde() {
+        let list = OwnedIntrusiveList::new();
+        for elem in 0..5 {
+            list.push_back(elem);
+        }
+
+        let json = serde_json::to_string(&list).unwrap();
+        assert_eq!("[0,1,2,3,4]", json);
+
+        let list: OwnedIntrusiveList<i32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            vec![0, 1, 2, 3, 4],
+            list.iter_elems().copied().collect::<Vec<_>>()
+        );
+
+        let list: OwnedIntrusiveList<i32> = serde_json::from_str("[]").unwrap();
+        assert!(list.is_empty());
+
+        assert!(serde_json::from_str::<OwnedIntrusiveList<i32>>("{}").is_err());
+    }
+}
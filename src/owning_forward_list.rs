@@ -0,0 +1,127 @@
+use core::marker::PhantomData;
+use core::fmt::{self, Debug, Formatter};
+
+use alloc::{boxed::Box, vec::Vec};
+
+use crate::intrusive_forward_list::*;
+use crate::utility::OwnedNode;
+
+type Node<T> = OwnedNode<IntrusiveForwardListNodeImpl<T>>;
+
+/// `OwningForwardList` is a wrapper of `IntrusiveForwardList` which allocates
+/// its nodes on heap, so that it can be used without `unsafe`.
+///
+/// Nodes are kept alive until they are removed or `self` is dropped.
+pub struct OwningForwardList<T: 'static> {
+    list: IntrusiveForwardList<'static, Node<T>>,
+    phantom: PhantomData<Box<Node<T>>>,
+}
+impl<T: Debug> Debug for OwningForwardList<T> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_list().entries(self.iter_elems()).finish()
+    }
+}
+impl<T> Default for OwningForwardList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<T> OwningForwardList<T> {
+    pub fn new() -> Self {
+        Self {
+            list: IntrusiveForwardList::new(),
+            phantom: PhantomData,
+        }
+    }
+
+    pub fn push_front(&self, elem: T) {
+        let node = Box::leak(Box::new(Node::new(IntrusiveForwardListNodeImpl::new(elem))));
+        unsafe { self.list.push_front(node) };
+    }
+
+    // All methods read the list:
+
+    pub fn iter_elems(&self) -> impl Iterator<Item = &T> + '_ {
+        self.list.iter().map(|node| -> &T { node.get_elem() })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    // All methods below are removal methods, which takes the write lock:
+
+    ///  * `f` - return true to remove the element or false to keep it
+    ///
+    /// Return (# num of elements left, # num of elements removed)
+    pub fn remove_if(&mut self, mut f: impl FnMut(&T) -> bool) -> (usize, usize) {
+        let mut removed = Vec::new();
+
+        let cnt = self.list.remove_if(|node| {
+            let remove = f(node.get_elem());
+            if remove {
+                removed.push(node as *const Node<T> as *mut Node<T>);
+            }
+            remove
+        });
+
+        for node in removed {
+            drop(unsafe { Box::from_raw(node) });
+        }
+
+        cnt
+    }
+
+    pub fn clear(&mut self) {
+        self.remove_if(|_| true);
+    }
+}
+impl<T> Drop for OwningForwardList<T> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+#[cfg(all(test, feature = "std", feature = "concurrency_toolkit"))]
+mod tests {
+    use super::*;
+
+    use std::rc::Rc;
+
+    #[concurrency_toolkit::test]
+    fn test_owning_forward_list_push_front() {
+        let list = OwningForwardList::new();
+        assert!(list.is_empty());
+
+        for elem in 0..100 {
+            list.push_front(elem);
+        }
+        assert!(!list.is_empty());
+
+        for (index, elem) in (0..100).rev().zip(list.iter_elems()) {
+            assert_eq!(index, *elem);
+        }
+        assert_eq!(100, list.iter_elems().count());
+    }
+
+    #[concurrency_toolkit::test]
+    fn test_owning_forward_list_remove_if() {
+        let counter = Rc::new(());
+
+        let mut list = OwningForwardList::new();
+        for elem in 0..10 {
+            list.push_front((elem, counter.clone()));
+        }
+        assert_eq!(11, Rc::strong_count(&counter));
+
+        assert_eq!((5, 5), list.remove_if(|(elem, _)| elem % 2 == 0));
+        assert_eq!(6, Rc::strong_count(&counter));
+        assert_eq!(
+            vec![9, 7, 5, 3, 1],
+            list.iter_elems().map(|(elem, _)| *elem).collect::<Vec<_>>()
+        );
+
+        drop(list);
+        assert_eq!(1, Rc::strong_count(&counter));
+    }
+}
@@ -1,13 +1,39 @@
-use concurrency_toolkit::atomic::{self, AtomicPtr, Ordering};
+#[cfg(feature = "concurrency_toolkit")]
+pub use concurrency_toolkit::atomic;
+
+/// Fallback of `concurrency_toolkit::atomic` for `no_std` targets.
+#[cfg(not(feature = "concurrency_toolkit"))]
+pub mod atomic {
+    pub use core::sync::atomic::*;
+
+    /// Atomic store that asserts `atomic` held `old_val` before storing
+    /// `new_val` in debug builds.
+    #[inline(always)]
+    pub fn assert_store_ptr<T>(
+        atomic: &AtomicPtr<T>,
+        _old_val: *mut T,
+        new_val: *mut T,
+        _debug_order: Ordering,
+        _release_order: Ordering
+    ) {
+        #[cfg(debug_assertions)]
+        assert_eq!(_old_val, atomic.swap(new_val, _debug_order));
+        #[cfg(not(debug_assertions))]
+        atomic.store(new_val, _release_order);
+    }
+}
+
+use atomic::{AtomicPtr, Ordering};
+
+#[cfg(feature = "alloc")]
+use crate::intrusive_forward_list::IntrusiveForwardListNode;
+#[cfg(feature = "alloc")]
+use crate::intrusive_list::IntrusiveListNode;
 
 pub const RW_ORD: Ordering = Ordering::AcqRel;
 pub const R_ORD: Ordering = Ordering::Acquire;
 pub const W_ORD: Ordering = Ordering::Release;
 
-pub fn assert_store_ptr<T>(atomic: &AtomicPtr<T>, old_val: *mut T, new_val: *mut T) {
-    atomic::assert_store_ptr(atomic, old_val, new_val, RW_ORD, W_ORD);
-}
-
 pub fn assert_store_ptr_relaxed<T>(
     atomic: &AtomicPtr<T>,
     old_val: *mut T,
@@ -17,3 +43,115 @@ pub fn assert_store_ptr_relaxed<T>(
 
     atomic::assert_store_ptr(atomic, old_val, new_val, Relaxed, Relaxed);
 }
+
+/// Called while waiting for another thread to finish its update of the list.
+#[inline(always)]
+pub fn spin_wait() {
+    #[cfg(feature = "permutation_testing")]
+    concurrency_toolkit::thread::yield_now();
+    #[cfg(not(feature = "permutation_testing"))]
+    core::hint::spin_loop();
+}
+
+/// Aligns `T` to the cache line to avoid false sharing.
+#[cfg(feature = "cache_padded")]
+#[repr(align(64))]
+#[derive(Debug, Default)]
+pub struct CachePadded<T>(T);
+#[cfg(feature = "cache_padded")]
+impl<T> core::ops::Deref for CachePadded<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+/// Type of `first_ptr` and `last_ptr` of `IntrusiveList`.
+#[cfg(feature = "cache_padded")]
+pub type EndPtr = CachePadded<AtomicPtr<()>>;
+/// Type of `first_ptr` and `last_ptr` of `IntrusiveList`.
+#[cfg(not(feature = "cache_padded"))]
+pub type EndPtr = AtomicPtr<()>;
+
+pub fn new_end_ptr() -> EndPtr {
+    #[cfg(feature = "cache_padded")]
+    {
+        CachePadded(AtomicPtr::new(core::ptr::null_mut()))
+    }
+    #[cfg(not(feature = "cache_padded"))]
+    {
+        AtomicPtr::new(core::ptr::null_mut())
+    }
+}
+
+/// Memory orderings used by `IntrusiveList`.
+///
+/// # Safety
+///
+/// The orderings must be strong enough to synchronize pushes with reads on
+/// the target platform, otherwise readers might observe partially initialized
+/// nodes.
+pub unsafe trait OrderingPolicy {
+    /// Ordering for loading links.
+    const R_ORD: Ordering;
+    /// Ordering for storing links.
+    const W_ORD: Ordering;
+    /// Ordering for read-modify-write operations on links.
+    const RW_ORD: Ordering;
+}
+
+/// Default `OrderingPolicy`, which uses `Acquire`, `Release` and `AcqRel`.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct DefaultOrdering;
+unsafe impl OrderingPolicy for DefaultOrdering {
+    const R_ORD: Ordering = R_ORD;
+    const W_ORD: Ordering = W_ORD;
+    const RW_ORD: Ordering = RW_ORD;
+}
+
+pub fn assert_store_ptr<P: OrderingPolicy, T>(
+    atomic: &AtomicPtr<T>,
+    old_val: *mut T,
+    new_val: *mut T
+) {
+    atomic::assert_store_ptr(atomic, old_val, new_val, P::RW_ORD, P::W_ORD);
+}
+
+/// Node allocated by the owning lists.
+///
+/// The lists only keep `&'static` references to their nodes, which must
+/// still grant write access to the whole node, including padding, for
+/// `Box::from_raw`, thus the node is wrapped in `UnsafeCell`.
+/// It is only mutated through its links, which are atomics.
+#[cfg(feature = "alloc")]
+pub struct OwnedNode<N>(core::cell::UnsafeCell<N>);
+#[cfg(feature = "alloc")]
+unsafe impl<N: Sync> Sync for OwnedNode<N> {}
+#[cfg(feature = "alloc")]
+impl<N> OwnedNode<N> {
+    pub fn new(node: N) -> Self {
+        Self(core::cell::UnsafeCell::new(node))
+    }
+
+    pub fn get(&self) -> &N {
+        unsafe { &*self.0.get() }
+    }
+}
+#[cfg(feature = "alloc")]
+unsafe impl<'a, N: IntrusiveForwardListNode<'a>> IntrusiveForwardListNode<'a> for OwnedNode<N> {
+    type Target = N::Target;
+
+    fn get_next_ptr(&self) -> &AtomicPtr<()> {
+        self.get().get_next_ptr()
+    }
+    fn get_elem(&'a self) -> Self::Target {
+        self.get().get_elem()
+    }
+}
+#[cfg(feature = "alloc")]
+unsafe impl<'a, N: IntrusiveListNode<'a>> IntrusiveListNode<'a> for OwnedNode<N> {
+    fn get_prev_ptr(&self) -> &AtomicPtr<()> {
+        self.get().get_prev_ptr()
+    }
+}